@@ -0,0 +1,26 @@
+use std::process::Command;
+
+// Records the commit the binary is built from, for the benchmark reports.
+fn main() {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|output| output.trim().to_owned())
+    };
+    if let Some(commit) = git(&["rev-parse", "--short", "HEAD"]) {
+        println!("cargo:rustc-env=AOC_BUILD_COMMIT={}", commit);
+    }
+    // Committing or checking out another commit moves HEAD or the refs.
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        let git_dir = std::path::Path::new(&git_dir);
+        for path in ["HEAD", "refs", "packed-refs"].map(|path| git_dir.join(path)) {
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+}
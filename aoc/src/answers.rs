@@ -0,0 +1,127 @@
+//! Known puzzle answers, kept in an `answers.toml` file keyed by year, day and
+//! part:
+//!
+//! ```toml
+//! [2022.day01]
+//! part1 = 24000
+//! part2 = 45000
+//!
+//! [2022.day05]
+//! part1 = "CMZ"
+//! ```
+
+use crate::day_tables::{self, parse_key, InvalidKey};
+use helpers::solution::{Answer, Part};
+use std::{collections::HashMap, path::Path};
+
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    answers: HashMap<(u32, u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn expected(&self, year: u32, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn matches(&self, year: u32, day: u8, part: Part, answer: &Answer) -> Option<bool> {
+        self.expected(year, day, part)
+            .map(|expected| normalize(expected) == normalize(&answer.to_string()))
+    }
+}
+
+// Multi-line answers are compared line by line, ignoring trailing whitespace.
+fn normalize(s: &str) -> String {
+    s.trim_matches('\n')
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl std::str::FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = HashMap::new();
+        for (year, day, parts) in day_tables::parse::<Error>(s)? {
+            for (part_key, value) in parts {
+                let part: Part = parse_key(&part_key, "part")?;
+                let value = match value {
+                    toml::Value::String(s) => s,
+                    toml::Value::Integer(n) => n.to_string(),
+                    _ => return Err(Error::InvalidAnswer(part_key)),
+                };
+                answers.insert((year, day, part), value);
+            }
+        }
+        Ok(Answers { answers })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error("invalid answers key '{0}', expected e.g. [2022.day01] and part1 = ...")]
+    InvalidKey(String),
+    #[error("answer of {0} must be a string or an integer")]
+    InvalidAnswer(String),
+}
+
+impl From<InvalidKey> for Error {
+    fn from(InvalidKey(key): InvalidKey) -> Self {
+        Error::InvalidKey(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_matches_answers() {
+        let answers: Answers = r#"
+[2022.day01]
+part1 = 24000
+part2 = "45000"
+
+[2022.day10]
+part2 = """
+##..
+#..#
+"""
+"#
+        .parse()
+        .unwrap();
+
+        assert_eq!(answers.expected(2022, 1, Part::One), Some("24000"));
+        assert_eq!(
+            answers.matches(2022, 1, Part::Two, &45000.into()),
+            Some(true)
+        );
+        assert_eq!(answers.matches(2022, 1, Part::One, &1.into()), Some(false));
+        assert_eq!(answers.matches(2022, 2, Part::One, &1.into()), None);
+
+        let crt = Answer::Multiline("##..  \n#..#\n".to_owned());
+        assert_eq!(answers.matches(2022, 10, Part::Two, &crt), Some(true));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!("[2022.day01]\npart3 = 1".parse::<Answers>().is_err());
+        assert!("[twenty.day01]\npart1 = 1".parse::<Answers>().is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{params::Param, registry::BoxedSolution, table::Table};
+use helpers::solution::Part;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let len = sorted.len();
+        let median = match len {
+            0 => return None,
+            _ if len.is_multiple_of(2) => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+            _ => sorted[len / 2],
+        };
+        // Nearest-rank percentile.
+        let p95_rank = (len * 95).div_ceil(100);
+        Some(Stats {
+            min: sorted[0],
+            median,
+            p95: sorted[p95_rank - 1],
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Measurement {
+    pub day: u8,
+    // "parse", "part1" or "part2".
+    pub phase: String,
+    pub iterations: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Measurement {
+    fn new(day: u8, phase: String, samples: &[Duration]) -> Option<Self> {
+        let stats = Stats::from_samples(samples)?;
+        Some(Measurement {
+            day,
+            phase,
+            iterations: samples.len(),
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            p95_ns: stats.p95.as_nanos() as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Report {
+    pub year: u32,
+    // Commit the measured binary was built from, when known.
+    pub commit: Option<String>,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn new(year: u32) -> Self {
+        Report {
+            year,
+            commit: option_env!("AOC_BUILD_COMMIT").map(str::to_owned),
+            measurements: vec![],
+        }
+    }
+
+    pub fn table(&self) -> Table {
+        let mut table = Table::new(&["day", "phase", "iterations", "min", "median", "p95"]);
+        let fmt = |ns: u64| format!("{:?}", Duration::from_nanos(ns));
+        self.measurements.iter().for_each(|m| {
+            table.add_row(vec![
+                format!("{:02}", m.day),
+                m.phase.clone(),
+                m.iterations.to_string(),
+                fmt(m.min_ns),
+                fmt(m.median_ns),
+                fmt(m.p95_ns),
+            ])
+        });
+        table
+    }
+}
+
+pub fn bench(
+    solution: &BoxedSolution,
+    input: &str,
+    parts: &[Part],
+    params: &[Param],
+    iterations: usize,
+) -> color_eyre::eyre::Result<Vec<Measurement>> {
+    let day = solution.day();
+    let samples = solution.sample(input, parts, params, iterations)?;
+    let parse = Measurement::new(day, "parse".to_owned(), &samples.parse);
+    let parts = samples
+        .parts
+        .iter()
+        .map(|(part, samples)| Measurement::new(day, format!("part{}", part), samples));
+    Ok(std::iter::once(parse).chain(parts).flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let samples = (1..=20)
+            .rev()
+            .map(Duration::from_millis)
+            .collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10_500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let stats = Stats::from_samples(&[Duration::from_millis(3)]).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(3));
+
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
@@ -0,0 +1,39 @@
+//! The `[YYYY.dayNN]` tables of the TOML files of the inputs store, like
+//! `answers.toml` and `params.toml`.
+
+use std::str::FromStr;
+
+/// A key that isn't a year, or a number after its prefix like `day09`.
+#[derive(Debug)]
+pub struct InvalidKey(pub String);
+
+/// Parses a key like `day09` or `part1`, the prefix being optional.
+pub fn parse_key<T: FromStr>(key: &str, prefix: &str) -> Result<T, InvalidKey> {
+    key.strip_prefix(prefix)
+        .unwrap_or(key)
+        .parse()
+        .map_err(|_| InvalidKey(key.to_owned()))
+}
+
+/// The table of each day, along with its year and day.
+pub fn parse<E>(s: &str) -> Result<Vec<(u32, u8, toml::value::Table)>, E>
+where
+    E: From<toml::de::Error> + From<InvalidKey>,
+{
+    let table: toml::value::Table = toml::from_str(s)?;
+    let mut day_tables = vec![];
+    for (year_key, days) in table {
+        let year: u32 = parse_key(&year_key, "")?;
+        let toml::Value::Table(days) = days else {
+            return Err(InvalidKey(year_key).into());
+        };
+        for (day_key, values) in days {
+            let day: u8 = parse_key(&day_key, "day")?;
+            let toml::Value::Table(values) = values else {
+                return Err(InvalidKey(day_key).into());
+            };
+            day_tables.push((year, day, values));
+        }
+    }
+    Ok(day_tables)
+}
@@ -0,0 +1,310 @@
+//! Puzzle input resolution.
+//!
+//! Inputs are kept in a local store laid out as
+//!
+//! ```text
+//! inputs/
+//!   2022/
+//!     day01.txt
+//!     day02.txt
+//!     ...
+//! ```
+//!
+//! The store defaults to the `inputs` directory at the workspace root and can
+//! be moved with the `AOC_INPUT_DIR` environment variable. When an input is
+//! missing and `AOC_SESSION` holds an adventofcode.com session cookie, the
+//! input is downloaded once and saved into the store.
+
+use helpers::solution::input_from_utf8;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Stands for stdin when passed as an input file.
+pub const STDIN_PATH: &str = "-";
+
+const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs");
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/alcroito/advent_of_code_2022 by placinta@gmail.com";
+
+pub trait InputProvider {
+    fn fetch(&self, year: u32, day: u8) -> Result<String, Error>;
+}
+
+/// Puzzle inputs saved on disk, one file per year and day.
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn from_env() -> Self {
+        match std::env::var_os(INPUT_DIR_ENV) {
+            Some(dir) => Self::new(dir),
+            None => Self::new(DEFAULT_INPUT_DIR),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn path_for(&self, year: u32, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    pub fn save(&self, year: u32, day: u8, contents: &str) -> Result<PathBuf, Error> {
+        let path = self.path_for(year, day);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
+
+impl InputProvider for InputStore {
+    fn fetch(&self, year: u32, day: u8) -> Result<String, Error> {
+        let path = self.path_for(year, day);
+        let bytes = std::fs::read(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::MissingInput { year, day, path },
+            _ => Error::Io(e),
+        })?;
+        Ok(input_from_utf8(&bytes)?.to_owned())
+    }
+}
+
+pub fn is_stdin(path: &Path) -> bool {
+    path.to_str() == Some(STDIN_PATH)
+}
+
+/// Reads an input file passed on the command line, or stdin for `-`.
+pub fn read_file(path: &Path) -> Result<String, Error> {
+    let mut bytes = vec![];
+    match is_stdin(path) {
+        true => std::io::stdin().read_to_end(&mut bytes)?,
+        false => std::fs::File::open(path)?.read_to_end(&mut bytes)?,
+    };
+    Ok(input_from_utf8(&bytes)?.to_owned())
+}
+
+// How an input file passed on the command line is named in the output.
+pub fn file_name(path: &Path) -> String {
+    match is_stdin(path) {
+        true => "<stdin>".to_owned(),
+        false => path.display().to_string(),
+    }
+}
+
+/// Downloads inputs from adventofcode.com, or from anything that serves the
+/// same `/{year}/day/{day}/input` routes.
+#[derive(Debug, Clone)]
+pub struct Downloader {
+    base_url: String,
+    session: String,
+}
+
+impl Downloader {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            session: session.into(),
+        }
+    }
+
+    pub fn from_env() -> Option<Self> {
+        let session = std::env::var(SESSION_ENV).ok()?;
+        let base_url = std::env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        Some(Self::new(base_url, session.trim()))
+    }
+
+    pub fn url_for(&self, year: u32, day: u8) -> String {
+        format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            year,
+            day
+        )
+    }
+}
+
+impl InputProvider for Downloader {
+    fn fetch(&self, year: u32, day: u8) -> Result<String, Error> {
+        let download_error = |source| Error::Download {
+            year,
+            day,
+            source: Box::new(source),
+        };
+        let mut bytes = vec![];
+        ureq::get(&self.url_for(year, day))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(download_error)?
+            .into_reader()
+            .read_to_end(&mut bytes)
+            .map_err(|e| download_error(e.into()))?;
+        Ok(input_from_utf8(&bytes)?.to_owned())
+    }
+}
+
+/// Reads inputs from the store, downloading and saving the missing ones when a
+/// downloader is available.
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    store: InputStore,
+    downloader: Option<Downloader>,
+}
+
+impl Inputs {
+    pub fn new(store: InputStore, downloader: Option<Downloader>) -> Self {
+        Self { store, downloader }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(InputStore::from_env(), Downloader::from_env())
+    }
+
+    pub fn store(&self) -> &InputStore {
+        &self.store
+    }
+}
+
+impl InputProvider for Inputs {
+    fn fetch(&self, year: u32, day: u8) -> Result<String, Error> {
+        match (self.store.fetch(year, day), &self.downloader) {
+            (Err(Error::MissingInput { .. }), Some(downloader)) => {
+                let input = downloader.fetch(year, day)?;
+                self.store.save(year, day, &input)?;
+                Ok(input)
+            }
+            (res, _) => res,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("input is not valid UTF-8")]
+    NotUtf8(#[from] std::str::Utf8Error),
+    #[error(
+        "no input for {year} day {day}, expected it at {}\n\
+         Save the input there, point {INPUT_DIR_ENV} at another inputs directory, \
+         set {SESSION_ENV} to your adventofcode.com session cookie to download it, \
+         or pass --input",
+        path.display()
+    )]
+    MissingInput { year: u32, day: u8, path: PathBuf },
+    #[error("failed to download input for {year} day {day}")]
+    Download {
+        year: u32,
+        day: u8,
+        #[source]
+        source: Box<ureq::Error>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    // Minimal stand-in for adventofcode.com, answering `requests` requests
+    // with the given body. Returns the base url and the received request lines.
+    fn serve(body: &'static str, requests: usize) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut seen = vec![];
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end().to_owned();
+                    if line.is_empty() {
+                        break;
+                    }
+                    seen.push(line);
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            seen
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn missing_input_is_reported_with_its_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        let err = store.fetch(2022, 7).unwrap_err();
+        let expected_path = dir.path().join("2022").join("day07.txt");
+        assert!(matches!(err, Error::MissingInput { ref path, .. } if *path == expected_path));
+        assert!(err.to_string().contains(SESSION_ENV));
+    }
+
+    #[test]
+    fn store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        store.save(2022, 1, "1000\n2000\n").unwrap();
+        assert_eq!(store.fetch(2022, 1).unwrap(), "1000\n2000\n");
+    }
+
+    #[test]
+    fn reads_input_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day06.txt");
+        std::fs::write(&path, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n").unwrap();
+        assert_eq!(
+            read_file(&path).unwrap(),
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"
+        );
+        assert!(read_file(&dir.path().join("missing.txt")).is_err());
+        std::fs::write(&path, b"R 4\n\xff\n").unwrap();
+        assert!(matches!(read_file(&path), Err(Error::NotUtf8(_))));
+
+        assert!(is_stdin(Path::new("-")));
+        assert_eq!(file_name(Path::new("-")), "<stdin>");
+        assert_eq!(file_name(&path), path.display().to_string());
+    }
+
+    #[test]
+    fn downloads_missing_input_once() {
+        let (base_url, server) = serve("R 4\nU 4\n", 1);
+        let dir = tempfile::tempdir().unwrap();
+        let inputs = Inputs::new(
+            InputStore::new(dir.path()),
+            Some(Downloader::new(base_url, "cookie")),
+        );
+
+        assert_eq!(inputs.fetch(2022, 9).unwrap(), "R 4\nU 4\n");
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2022/day/9/input HTTP/1.1");
+        assert!(request.iter().any(|l| l == "Cookie: session=cookie"));
+
+        // The server is gone, so this can only be served from the store.
+        assert_eq!(inputs.fetch(2022, 9).unwrap(), "R 4\nU 4\n");
+    }
+}
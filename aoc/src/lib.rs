@@ -0,0 +1,13 @@
+pub mod answers;
+pub mod bench;
+pub mod day_tables;
+pub mod input;
+pub mod output;
+pub mod parallel;
+pub mod params;
+pub mod registry;
+pub mod scaffold;
+pub mod table;
+pub mod trace;
+pub mod verify;
+pub mod watch;
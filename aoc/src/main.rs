@@ -0,0 +1,517 @@
+use aoc::{
+    answers::{Answers, ANSWERS_FILE_NAME},
+    bench::{self, Report},
+    input::{self, InputProvider, Inputs},
+    output::{Format, Record},
+    parallel,
+    params::{self, Config, Param, PARAMS_FILE_NAME},
+    registry::{self, BoxedSolution},
+    scaffold,
+    trace::{self, TraceGuard},
+    verify,
+    watch::{self, PartResult, Watcher},
+};
+use helpers::solution::{Answer, Part};
+
+use color_eyre::eyre::{eyre, Result};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "aoc", about = "Advent of Code solutions runner")]
+enum Command {
+    /// run the solution of one day, or of all registered days
+    Run(RunArgs),
+    /// check the answers of all registered days against the known answers
+    Verify(VerifyArgs),
+    /// time repeated runs of the parse step and of each part
+    Bench(BenchArgs),
+    /// create the crate of a new day and register it
+    NewDay(NewDayArgs),
+    /// print a random valid input of a day
+    Generate(GenerateArgs),
+    /// re-run the solution of a day whenever its input file changes
+    Watch(WatchArgs),
+}
+
+#[derive(StructOpt, Debug)]
+struct RunArgs {
+    /// puzzle year, defaults to the latest year with registered solutions
+    #[structopt(long)]
+    year: Option<u32>,
+
+    /// day to run
+    #[structopt(long, required_unless = "all")]
+    day: Option<u8>,
+
+    /// run every registered day
+    #[structopt(long, conflicts_with = "day")]
+    all: bool,
+
+    /// only run the given part (1 or 2), instead of both
+    #[structopt(long)]
+    part: Option<Part>,
+
+    /// input files, or - for stdin, defaults to the day's input from the
+    /// inputs store. Each file is solved on its own
+    #[structopt(long, parse(from_os_str), conflicts_with = "all")]
+    input: Vec<PathBuf>,
+
+    /// run the days in parallel and print a summary table
+    #[structopt(long, requires = "all")]
+    parallel: bool,
+
+    /// output format
+    #[structopt(long, default_value = "text", possible_values = &Format::NAMES)]
+    format: Format,
+
+    /// puzzle parameter overriding its default, like knots=25. Can be
+    /// passed several times
+    #[structopt(long = "param", parse(try_from_str = params::parse_param), requires = "day")]
+    params: Vec<Param>,
+
+    /// puzzle parameters file, defaults to params.toml in the inputs store
+    #[structopt(long, parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    /// time the phases of the run, printing a timing tree to stderr, or
+    /// writing a Chrome trace JSON when given a file
+    #[structopt(long)]
+    trace: Option<Option<PathBuf>>,
+}
+
+#[derive(StructOpt, Debug)]
+struct VerifyArgs {
+    /// puzzle year, defaults to the latest year with registered solutions
+    #[structopt(long)]
+    year: Option<u32>,
+
+    /// only verify the given day
+    #[structopt(long)]
+    day: Option<u8>,
+
+    /// known answers file, defaults to answers.toml in the inputs store
+    #[structopt(long, parse(from_os_str))]
+    answers: Option<PathBuf>,
+}
+
+#[derive(StructOpt, Debug)]
+struct BenchArgs {
+    /// puzzle year, defaults to the latest year with registered solutions
+    #[structopt(long)]
+    year: Option<u32>,
+
+    /// only benchmark the given day
+    #[structopt(long)]
+    day: Option<u8>,
+
+    /// only benchmark the given part (1 or 2), instead of both
+    #[structopt(long)]
+    part: Option<Part>,
+
+    /// number of timed runs of each phase
+    #[structopt(long, default_value = "20")]
+    iterations: usize,
+
+    /// also write the measurements as JSON to the given file
+    #[structopt(long, parse(from_os_str))]
+    json: Option<PathBuf>,
+
+    /// puzzle parameters file, defaults to params.toml in the inputs store
+    #[structopt(long, parse(from_os_str))]
+    config: Option<PathBuf>,
+}
+
+#[derive(StructOpt, Debug)]
+struct NewDayArgs {
+    /// puzzle year, defaults to the latest year with registered solutions
+    #[structopt(long)]
+    year: Option<u32>,
+
+    /// day to create
+    #[structopt(long)]
+    day: u8,
+}
+
+#[derive(StructOpt, Debug)]
+struct GenerateArgs {
+    /// puzzle year, defaults to the latest year with registered solutions
+    #[structopt(long)]
+    year: Option<u32>,
+
+    /// day to generate an input for
+    #[structopt(long)]
+    day: u8,
+
+    /// seed of the random number generator, the same seed generates the same input
+    #[structopt(long, default_value = "0")]
+    seed: u64,
+
+    /// rough number of lines or items in the input
+    #[structopt(long, default_value = "1000")]
+    size: usize,
+}
+
+#[derive(StructOpt, Debug)]
+struct WatchArgs {
+    /// puzzle year, defaults to the latest year with registered solutions
+    #[structopt(long)]
+    year: Option<u32>,
+
+    /// day to run
+    #[structopt(long)]
+    day: u8,
+
+    /// only run the given part (1 or 2), instead of both
+    #[structopt(long)]
+    part: Option<Part>,
+
+    /// input file to watch, defaults to the day's input from the inputs store
+    #[structopt(long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// milliseconds between checks of the input file
+    #[structopt(long, default_value = "200")]
+    interval: u64,
+
+    /// puzzle parameter overriding its default, like knots=25. Can be
+    /// passed several times
+    #[structopt(long = "param", parse(try_from_str = params::parse_param), requires = "day")]
+    params: Vec<Param>,
+
+    /// puzzle parameters file, defaults to params.toml in the inputs store
+    #[structopt(long, parse(from_os_str))]
+    config: Option<PathBuf>,
+}
+
+fn select_year(year: Option<u32>) -> Result<u32> {
+    match year {
+        Some(year) => Ok(year),
+        None => registry::years()
+            .last()
+            .copied()
+            .ok_or_else(|| eyre!("no solutions registered, pass --year")),
+    }
+}
+
+fn select_solutions(year: u32, day: Option<u8>) -> Result<Vec<BoxedSolution>> {
+    match day {
+        Some(day) => registry::find(year, day)
+            .map(|s| vec![s])
+            .ok_or_else(|| eyre!("no solution registered for {} day {}", year, day)),
+        None => match registry::solutions_of(year) {
+            solutions if solutions.is_empty() => Err(eyre!("no solutions registered for {}", year)),
+            solutions => Ok(solutions),
+        },
+    }
+}
+
+fn load_config(inputs: &Inputs, path: &Option<PathBuf>) -> Result<Config> {
+    let path = match path {
+        Some(ref path) => path.clone(),
+        None => inputs.store().root().join(PARAMS_FILE_NAME),
+    };
+    Config::load(&path).map_err(|e| eyre!("can't load {}: {}", path.display(), e))
+}
+
+impl RunArgs {
+    // The inputs to solve, along with their names.
+    fn inputs(&self, inputs: &Inputs, year: u32, day: u8) -> Result<Vec<(String, String)>> {
+        if self.input.is_empty() {
+            let name = inputs.store().path_for(year, day).display().to_string();
+            let _span = tracing::info_span!("read", input = %name).entered();
+            return Ok(vec![(name.clone(), inputs.fetch(year, day)?)]);
+        }
+        self.input
+            .iter()
+            .map(|path| {
+                let name = input::file_name(path);
+                let _span = tracing::info_span!("read", input = %name).entered();
+                Ok((name.clone(), input::read_file(path)?))
+            })
+            .collect()
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+fn print_answer(part: Part, answer: &Answer, elapsed: Duration) {
+    match answer.is_multiline() {
+        true => println!("p{}: ({:?})\n{}", part, elapsed, answer),
+        false => println!("p{}: {} ({:?})", part, answer, elapsed),
+    }
+}
+
+// Malformed input is shown like a compiler diagnostic, pointing at the
+// offending part of the input file.
+fn print_error(report: &color_eyre::Report, file_name: &str) {
+    let diagnostic = report
+        .downcast_ref::<helpers::error::Error>()
+        .and_then(|e| e.diagnostic(file_name));
+    match diagnostic {
+        Some(diagnostic) => eprint!("{}", diagnostic),
+        None => eprintln!("error: {:#}", report),
+    }
+}
+
+fn print_ndjson(records: &[Record]) -> Result<()> {
+    for record in records {
+        println!("{}", serde_json::to_string(record)?);
+    }
+    Ok(())
+}
+
+// Runs within a root span, printing the timing tree of the run or writing
+// its Chrome trace when asked to.
+fn traced(trace: &Option<Option<PathBuf>>, f: impl FnOnce() -> Result<()>) -> Result<()> {
+    let guard = match trace {
+        Some(path) => Some(trace::install(&path.clone().into())?),
+        None => None,
+    };
+    let result = tracing::info_span!("run").in_scope(f);
+    if let Some(tree) = guard.and_then(TraceGuard::finish) {
+        eprint!("{}", tree);
+    }
+    result
+}
+
+fn run(args: &RunArgs) -> Result<()> {
+    if args
+        .input
+        .iter()
+        .filter(|path| input::is_stdin(path))
+        .count()
+        > 1
+    {
+        return Err(eyre!("stdin can only be passed once as --input"));
+    }
+
+    if args.parallel {
+        return run_parallel(args);
+    }
+
+    let year = select_year(args.year)?;
+    let text = args.format == Format::Text;
+    let inputs = Inputs::from_env();
+    let config = load_config(&inputs, &args.config)?;
+    let (mut runs, mut failures) = (0, 0);
+    let mut records = vec![];
+    for solution in select_solutions(year, args.day)? {
+        let day = solution.day();
+        let params = config.for_day(year, day, &args.params);
+        let _span = tracing::info_span!("day", day).entered();
+        if args.all && text {
+            println!("day {:02}", day);
+        }
+        for (name, input) in args.inputs(&inputs, year, day)? {
+            if args.input.len() > 1 && text {
+                println!("{}", name);
+            }
+            runs += 1;
+            let solved = tracing::info_span!("solve", input = %name)
+                .in_scope(|| solution.solve_with(&input, &args.parts(), &params));
+            let outcome = match solved {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    match text {
+                        true => print_error(&e, &name),
+                        false => {
+                            records.extend(Record::from_parse_error(day, &name, &args.parts(), &e))
+                        }
+                    }
+                    failures += 1;
+                    continue;
+                }
+            };
+            failures += outcome.parts.iter().filter(|p| p.answer.is_err()).count();
+            if !text {
+                records.extend(Record::from_outcome(day, &name, &outcome));
+                continue;
+            }
+
+            println!("parse: ({:?})", outcome.parse_elapsed);
+            for part in outcome.parts {
+                match part.answer {
+                    Ok(answer) => print_answer(part.part, &answer, part.elapsed),
+                    Err(e) => print_error(&e, &name),
+                }
+            }
+        }
+        if args.format == Format::Ndjson {
+            print_ndjson(&records)?;
+            records.clear();
+        }
+    }
+    if args.format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&records)?);
+    }
+
+    match failures {
+        0 => Ok(()),
+        failures => Err(eyre!("{} of {} runs failed", failures, runs)),
+    }
+}
+
+fn run_parallel(args: &RunArgs) -> Result<()> {
+    let year = select_year(args.year)?;
+    let inputs = Inputs::from_env();
+    let config = load_config(&inputs, &args.config)?;
+    let solutions = select_solutions(year, None)?;
+    let runs = parallel::run_all(&solutions, &inputs, &config, &args.parts());
+    let records = || {
+        runs.iter().flat_map(|run| {
+            let input = inputs.store().path_for(year, run.day);
+            run.records(&input.display().to_string(), &args.parts())
+        })
+    };
+    match args.format {
+        Format::Text => print!("{}", parallel::summary_table(&runs, &args.parts())),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&records().collect::<Vec<_>>())?
+        ),
+        Format::Ndjson => print_ndjson(&records().collect::<Vec<_>>())?,
+    }
+
+    match runs.iter().filter(|run| run.is_failure()).count() {
+        0 => Ok(()),
+        failures => Err(eyre!("{} of {} days failed", failures, runs.len())),
+    }
+}
+
+fn verify(args: &VerifyArgs) -> Result<()> {
+    let inputs = Inputs::from_env();
+    let answers_path = match args.answers {
+        Some(ref path) => path.clone(),
+        None => inputs.store().root().join(ANSWERS_FILE_NAME),
+    };
+    let answers = Answers::load(&answers_path)?;
+
+    let year = select_year(args.year)?;
+    let checks = verify::verify(&select_solutions(year, args.day)?, &inputs, &answers);
+    print!("{}", verify::checks_table(&checks));
+
+    match checks.iter().filter(|c| c.is_failure()).count() {
+        0 => Ok(()),
+        failures => Err(eyre!("{} of {} checks failed", failures, checks.len())),
+    }
+}
+
+fn bench(args: &BenchArgs) -> Result<()> {
+    let inputs = Inputs::from_env();
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let year = select_year(args.year)?;
+    let config = load_config(&inputs, &args.config)?;
+    let mut report = Report::new(year);
+    for solution in select_solutions(year, args.day)? {
+        let day = solution.day();
+        let params = config.for_day(year, day, &[]);
+        let measurements = inputs
+            .fetch(year, day)
+            .map_err(Into::into)
+            .and_then(|input| bench::bench(&solution, &input, &parts, &params, args.iterations));
+        match measurements {
+            Ok(measurements) => report.measurements.extend(measurements),
+            Err(e) => eprintln!("skipping day {:02}: {}", day, e),
+        }
+    }
+    print!("{}", report.table());
+
+    if let Some(ref path) = args.json {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+    Ok(())
+}
+
+fn new_day(args: &NewDayArgs) -> Result<()> {
+    let root = Path::new(scaffold::WORKSPACE_ROOT).canonicalize()?;
+    let year = select_year(args.year)?;
+    if registry::find(year, args.day).is_some() {
+        return Err(eyre!("{} day {} is already registered", year, args.day));
+    }
+    let dir = scaffold::new_day(&root, year, args.day)?;
+    println!("created {}", dir.display());
+    Ok(())
+}
+
+fn generate(args: &GenerateArgs) -> Result<()> {
+    let solution = select_solutions(select_year(args.year)?, Some(args.day))?.remove(0);
+    let input = solution
+        .generate(args.seed, args.size)
+        .ok_or_else(|| eyre!("day {} has no input generator", args.day))?;
+    print!("{}", input);
+    Ok(())
+}
+
+fn watch(args: &WatchArgs) -> Result<()> {
+    let year = select_year(args.year)?;
+    let solution = select_solutions(year, Some(args.day))?.remove(0);
+    let inputs = Inputs::from_env();
+    let params = load_config(&inputs, &args.config)?.for_day(year, args.day, &args.params);
+    let path = match args.input {
+        Some(ref path) if input::is_stdin(path) => {
+            return Err(eyre!("stdin can't be watched, pass an input file"))
+        }
+        Some(ref path) => path.clone(),
+        None => {
+            // Downloads the input if it is not in the store yet.
+            inputs.fetch(year, args.day)?;
+            inputs.store().path_for(year, args.day)
+        }
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let name = input::file_name(&path);
+    let mut watcher = Watcher::new(path);
+    let mut previous = vec![];
+    println!("watching {}, press Ctrl-C to stop", name);
+    loop {
+        if let Some(input) = watcher.poll()? {
+            println!();
+            match solution.solve_with(input, &parts, &params) {
+                Ok(outcome) => {
+                    println!("parse: ({:?})", outcome.parse_elapsed);
+                    let current = PartResult::from_outcome(&outcome);
+                    print!("{}", watch::changes_table(&previous, &current));
+                    for e in outcome.parts.iter().filter_map(|p| p.answer.as_ref().err()) {
+                        print_error(e, &name);
+                    }
+                    previous = current;
+                }
+                Err(e) => print_error(&e, &name),
+            }
+        }
+        std::thread::sleep(Duration::from_millis(args.interval));
+    }
+}
+
+fn main() -> Result<()> {
+    // The spans recorded by --trace aren't captured in error reports, which
+    // color-eyre would otherwise warn about.
+    color_eyre::config::HookBuilder::default()
+        .capture_span_trace_by_default(false)
+        .install()?;
+    match Command::from_args() {
+        Command::Run(args) => traced(&args.trace, || run(&args)),
+        Command::Verify(args) => verify(&args),
+        Command::Bench(args) => bench(&args),
+        Command::NewDay(args) => new_day(&args),
+        Command::Generate(args) => generate(&args),
+        Command::Watch(args) => watch(&args),
+    }
+}
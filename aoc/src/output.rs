@@ -0,0 +1,137 @@
+//! Machine readable output of the runner, one record per day, input and part.
+
+use color_eyre::Report;
+use helpers::solution::{Outcome, Part};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    // A single JSON array of records.
+    Json,
+    // One JSON record per line, printed as soon as it is available.
+    Ndjson,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 3] = ["text", "json", "ndjson"];
+}
+
+impl std::str::FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => Err(Error::UnknownFormat(s.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+    // Missing when the input could not be parsed.
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    // The error and its causes, outermost first.
+    pub error: Option<Vec<String>>,
+}
+
+fn nanos(elapsed: Duration) -> Option<u64> {
+    Some(elapsed.as_nanos() as u64)
+}
+
+fn error_chain(report: &Report) -> Option<Vec<String>> {
+    Some(report.chain().map(|e| e.to_string()).collect())
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+impl Record {
+    pub fn from_outcome(day: u8, input: &str, outcome: &Outcome) -> Vec<Record> {
+        outcome
+            .parts
+            .iter()
+            .map(|part| Record {
+                day,
+                part: part_number(part.part),
+                input: input.to_owned(),
+                answer: part.answer.as_ref().ok().map(ToString::to_string),
+                parse_ns: nanos(outcome.parse_elapsed),
+                solve_ns: nanos(part.elapsed),
+                error: part.answer.as_ref().err().and_then(error_chain),
+            })
+            .collect()
+    }
+
+    // Records of parts that could not be solved because parsing failed.
+    pub fn from_parse_error(day: u8, input: &str, parts: &[Part], report: &Report) -> Vec<Record> {
+        parts
+            .iter()
+            .map(|&part| Record {
+                day,
+                part: part_number(part),
+                input: input.to_owned(),
+                answer: None,
+                parse_ns: None,
+                solve_ns: None,
+                error: error_chain(report),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("unknown format '{0}', expected text, json or ndjson")]
+    UnknownFormat(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::solution::Solution;
+
+    #[test]
+    fn records_answers_and_errors() {
+        let outcome = day02::Day02.solve("A Y\nB X\nC Z\n", &Part::ALL).unwrap();
+        let records = Record::from_outcome(2, "example.txt", &outcome);
+        let answers = records
+            .iter()
+            .map(|r| (r.part, r.answer.as_deref(), r.error.is_none()))
+            .collect::<Vec<_>>();
+        assert_eq!(answers, [(1, Some("15"), true), (2, Some("12"), true)]);
+        assert!(records.iter().all(|r| r.parse_ns.is_some()));
+
+        let report = day02::Day02.solve("A Q\n", &Part::ALL).unwrap_err();
+        let records = Record::from_parse_error(2, "bad.txt", &[Part::Two], &report);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].answer, None);
+        assert_eq!(records[0].parse_ns, None);
+        let error = records[0].error.as_ref().unwrap();
+        assert_eq!(error[0], "malformed input at line 1, column 3: \"Q\"");
+        assert_eq!(error.len(), 2);
+
+        let json = serde_json::to_value(&records[0]).unwrap();
+        assert_eq!(json["part"], 2);
+        assert_eq!(json["input"], "bad.txt");
+        assert!(json["answer"].is_null());
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("ndjson".parse::<Format>().unwrap(), Format::Ndjson);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Runs several days at once on a thread pool. A failing or panicking day is
+//! reported along with the others instead of stopping the run.
+
+use crate::{
+    input::InputProvider, output::Record, params::Config, registry::BoxedSolution, table::Table,
+};
+use color_eyre::Report;
+use helpers::solution::{Outcome, Part};
+use rayon::prelude::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[derive(Debug)]
+pub enum Run {
+    Solved(Outcome),
+    Failed(Report),
+    Panicked(String),
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: u8,
+    pub run: Run,
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_owned())
+}
+
+fn run_day(
+    parent: &tracing::Span,
+    solution: &BoxedSolution,
+    inputs: &(dyn InputProvider + Sync),
+    config: &Config,
+    parts: &[Part],
+) -> DayRun {
+    let day = solution.day();
+    let params = config.for_day(solution.year(), day, &[]);
+    let _span = tracing::info_span!(parent: parent, "day", day).entered();
+    let input = tracing::info_span!("read").in_scope(|| inputs.fetch(solution.year(), day));
+    let run = match input {
+        Err(e) => Run::Failed(e.into()),
+        Ok(input) => match catch_unwind(AssertUnwindSafe(|| {
+            let _span = tracing::info_span!("solve").entered();
+            solution.solve_with(&input, parts, &params)
+        })) {
+            Ok(Ok(outcome)) => Run::Solved(outcome),
+            Ok(Err(e)) => Run::Failed(e),
+            Err(payload) => Run::Panicked(panic_message(payload)),
+        },
+    };
+    DayRun { day, run }
+}
+
+/// Runs the given days in parallel, returning their runs in the same order.
+pub fn run_all(
+    solutions: &[BoxedSolution],
+    inputs: &(dyn InputProvider + Sync),
+    config: &Config,
+    parts: &[Part],
+) -> Vec<DayRun> {
+    // The spans of the days are opened on the threads of the pool.
+    let parent = tracing::Span::current();
+    solutions
+        .par_iter()
+        .map(|solution| run_day(&parent, solution, inputs, config, parts))
+        .collect()
+}
+
+impl DayRun {
+    pub fn is_failure(&self) -> bool {
+        match self.run {
+            Run::Solved(ref outcome) => outcome.parts.iter().any(|p| p.answer.is_err()),
+            Run::Failed(_) | Run::Panicked(_) => true,
+        }
+    }
+
+    pub fn records(&self, input: &str, parts: &[Part]) -> Vec<Record> {
+        match self.run {
+            Run::Solved(ref outcome) => Record::from_outcome(self.day, input, outcome),
+            Run::Failed(ref e) => Record::from_parse_error(self.day, input, parts, e),
+            Run::Panicked(ref message) => {
+                let e = color_eyre::eyre::eyre!("panicked: {}", message);
+                Record::from_parse_error(self.day, input, parts, &e)
+            }
+        }
+    }
+}
+
+// The outermost error only, the whole chain is in the records.
+fn error_summary(e: &Report) -> String {
+    e.to_string().lines().next().unwrap_or_default().to_owned()
+}
+
+pub fn summary_table(runs: &[DayRun], parts: &[Part]) -> Table {
+    let mut table = Table::new(&["day", "part", "answer", "time", "status"]);
+    for day_run in runs {
+        let day = format!("{:02}", day_run.day);
+        let mut add_row = |part: Part, answer: String, time: String, status: &str| {
+            table.add_row(vec![
+                day.clone(),
+                part.to_string(),
+                answer,
+                time,
+                status.to_owned(),
+            ])
+        };
+        match day_run.run {
+            Run::Solved(ref outcome) => outcome.parts.iter().for_each(|p| {
+                let time = format!("{:?}", p.elapsed);
+                match p.answer {
+                    Ok(ref answer) => add_row(p.part, answer.to_string(), time, "ok"),
+                    Err(ref e) => add_row(p.part, error_summary(e), time, "ERROR"),
+                }
+            }),
+            Run::Failed(ref e) => parts
+                .iter()
+                .for_each(|&part| add_row(part, error_summary(e), "-".to_owned(), "ERROR")),
+            Run::Panicked(ref message) => parts
+                .iter()
+                .for_each(|&part| add_row(part, message.clone(), "-".to_owned(), "PANIC")),
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{Error, InputStore};
+    use helpers::solution::{Answer, Puzzle};
+
+    struct Panics;
+
+    impl Puzzle for Panics {
+        const YEAR: u32 = 2022;
+        const DAY: u8 = 24;
+
+        type Input = ();
+        type Params = ();
+
+        fn parse(&self, _: &str) -> color_eyre::eyre::Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part1(&self, _: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+            panic!("out of bounds")
+        }
+
+        fn part2(&self, _: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+            Ok(0.into())
+        }
+    }
+
+    #[test]
+    fn reports_failures_and_panics() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        store.save(2022, 2, "A Y\nB X\nC Z\n").unwrap();
+        store.save(2022, 4, "1-2,x-4\n").unwrap();
+        store.save(2022, 24, "").unwrap();
+        let solutions: Vec<BoxedSolution> = vec![
+            Box::new(day02::Day02),
+            Box::new(day04::Day04),
+            Box::new(day06::Day06),
+            Box::new(Panics),
+        ];
+
+        let runs = run_all(&solutions, &store, &Config::default(), &Part::ALL);
+        assert_eq!(
+            runs.iter().map(|r| r.day).collect::<Vec<_>>(),
+            [2, 4, 6, 24]
+        );
+        assert!(matches!(runs[0].run, Run::Solved(_)));
+        assert!(!runs[0].is_failure());
+        assert!(matches!(runs[1].run, Run::Failed(_)));
+        assert!(matches!(
+            runs[2].run,
+            Run::Failed(ref e) if matches!(e.downcast_ref(), Some(Error::MissingInput { .. }))
+        ));
+        assert!(matches!(runs[3].run, Run::Panicked(ref m) if m == "out of bounds"));
+        assert!(runs[1..].iter().all(DayRun::is_failure));
+
+        let table = summary_table(&runs, &Part::ALL).to_string();
+        let statuses = table
+            .lines()
+            .skip(2)
+            .map(|l| l.rsplit(" | ").next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            ["ok", "ok", "ERROR", "ERROR", "ERROR", "ERROR", "PANIC", "PANIC"]
+        );
+    }
+}
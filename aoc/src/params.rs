@@ -0,0 +1,145 @@
+//! Puzzle parameters overriding the defaults of the puzzles, kept in a
+//! `params.toml` file keyed by year and day:
+//!
+//! ```toml
+//! [2022.day09]
+//! knots = 25
+//!
+//! [2022.day06]
+//! marker_len = [4, 20]
+//! ```
+//!
+//! Arrays give one value per part. Values passed with `--param` take
+//! precedence over the ones of the file.
+
+use crate::day_tables::{self, InvalidKey};
+use std::{collections::HashMap, path::Path};
+
+pub const PARAMS_FILE_NAME: &str = "params.toml";
+
+pub type Param = (String, String);
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    params: HashMap<(u32, u8), Vec<Param>>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The parameters of a day, with `overrides` replacing the values of the
+    /// same name from the file.
+    pub fn for_day(&self, year: u32, day: u8, overrides: &[Param]) -> Vec<Param> {
+        let mut params = self.params.get(&(year, day)).cloned().unwrap_or_default();
+        params.retain(|(name, _)| overrides.iter().all(|(o, _)| o != name));
+        params.extend(overrides.iter().cloned());
+        params
+    }
+}
+
+/// Parses a `name=value` parameter given on the command line.
+pub fn parse_param(s: &str) -> Result<Param, Error> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_owned(), value.trim().to_owned()))
+        }
+        _ => Err(Error::InvalidParam(s.to_owned())),
+    }
+}
+
+fn value_to_string(name: &str, value: &toml::Value) -> Result<String, Error> {
+    match value {
+        toml::Value::String(s) => Ok(s.to_owned()),
+        toml::Value::Integer(n) => Ok(n.to_string()),
+        toml::Value::Float(n) => Ok(n.to_string()),
+        toml::Value::Boolean(b) => Ok(b.to_string()),
+        toml::Value::Array(values) if !values.is_empty() => Ok(values
+            .iter()
+            .map(|v| value_to_string(name, v))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(",")),
+        _ => Err(Error::InvalidValue(name.to_owned())),
+    }
+}
+
+impl std::str::FromStr for Config {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut params = HashMap::new();
+        for (year, day, values) in day_tables::parse::<Error>(s)? {
+            let day_params = values
+                .iter()
+                .map(|(name, value)| Ok((name.to_owned(), value_to_string(name, value)?)))
+                .collect::<Result<Vec<_>, Error>>()?;
+            params.insert((year, day), day_params);
+        }
+        Ok(Config { params })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error("invalid params key '{0}', expected e.g. [2022.day09] and knots = ...")]
+    InvalidKey(String),
+    #[error("parameter {0} must be a string, a number, a boolean or an array of them")]
+    InvalidValue(String),
+    #[error("invalid parameter '{0}', expected name=value")]
+    InvalidParam(String),
+}
+
+impl From<InvalidKey> for Error {
+    fn from(InvalidKey(key): InvalidKey) -> Self {
+        Error::InvalidKey(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str, value: &str) -> Param {
+        (name.to_owned(), value.to_owned())
+    }
+
+    #[test]
+    fn parses_and_overrides_params() {
+        let config: Config = r#"
+[2022.day06]
+marker_len = [4, 20]
+
+[2022.day09]
+knots = 25
+"#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.for_day(2022, 6, &[]), [param("marker_len", "4,20")]);
+        assert_eq!(
+            config.for_day(2022, 9, &[param("knots", "3")]),
+            [param("knots", "3")]
+        );
+        assert_eq!(config.for_day(2022, 1, &[]), []);
+
+        assert_eq!(parse_param(" knots = 25").unwrap(), param("knots", "25"));
+        assert!(parse_param("knots").is_err());
+        assert!(parse_param("=25").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!("[2022.day09]\nknots = []".parse::<Config>().is_err());
+        assert!("[2022.day09]\nknots = { a = 1 }".parse::<Config>().is_err());
+        assert!("[2022.nine]\nknots = 1".parse::<Config>().is_err());
+    }
+}
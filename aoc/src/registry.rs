@@ -0,0 +1,55 @@
+use helpers::solution::Solution;
+
+pub type BoxedSolution = Box<dyn Solution + Send + Sync>;
+
+// Every day crate registers its solution here, in year and day order, under
+// a comment with the year.
+pub fn solutions() -> Vec<BoxedSolution> {
+    vec![
+        // 2022
+        Box::new(day01::Day01),
+        Box::new(day02::Day02),
+        Box::new(day03::Day03),
+        Box::new(day04::Day04),
+        Box::new(day05::Day05),
+        Box::new(day06::Day06),
+        Box::new(day07::Day07),
+        Box::new(day08::Day08),
+        Box::new(day09::Day09),
+    ]
+}
+
+/// The years that have at least one registered solution, in order.
+pub fn years() -> Vec<u32> {
+    let mut years = solutions().iter().map(|s| s.year()).collect::<Vec<_>>();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
+
+pub fn solutions_of(year: u32) -> Vec<BoxedSolution> {
+    solutions()
+        .into_iter()
+        .filter(|s| s.year() == year)
+        .collect()
+}
+
+pub fn find(year: u32, day: u8) -> Option<BoxedSolution> {
+    solutions()
+        .into_iter()
+        .find(|s| s.year() == year && s.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_solutions_by_year() {
+        assert!(years().contains(&2022));
+        assert_eq!(find(2022, 9).map(|s| s.day()), Some(9));
+        assert!(find(2015, 9).is_none());
+        assert!(solutions_of(2015).is_empty());
+        assert!(solutions_of(2022).iter().all(|s| s.year() == 2022));
+    }
+}
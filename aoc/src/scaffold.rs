@@ -0,0 +1,336 @@
+//! Creates the crate of a new day from the templates in `templates/day`, and
+//! wires it into the workspace and the runner registry.
+
+use std::path::{Path, PathBuf};
+
+pub const WORKSPACE_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/day/Cargo.toml.in");
+const LIB_TEMPLATE: &str = include_str!("../templates/day/lib.rs.in");
+
+// The days of 2022 came before days were kept by year, and stay at the root of
+// the workspace. The days of other years live in their own `yYYYY` directory.
+const ROOT_YEAR: u32 = 2022;
+
+// Where the crate of a day is, relative to the workspace root.
+fn crate_dir(year: u32, day: u8) -> String {
+    match year == ROOT_YEAR {
+        true => format!("day{:02}", day),
+        false => format!("y{}/day{:02}", year, day),
+    }
+}
+
+fn crate_name(year: u32, day: u8) -> String {
+    crate_dir(year, day).replace('/', "_")
+}
+
+fn render(template: &str, year: u32, day: u8) -> String {
+    // The way back to the workspace root from the crate of the day.
+    let workspace = vec![".."; crate_dir(year, day).split('/').count()].join("/");
+    template
+        .replace("{{name}}", &crate_name(year, day))
+        .replace("{{type}}", &format!("Day{:02}", day))
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+        .replace("{{workspace}}", &workspace)
+}
+
+// Inserts `line` after the last line for which `is_anchor` holds.
+fn insert_after_last(
+    contents: &str,
+    is_anchor: impl Fn(&str) -> bool,
+    line: &str,
+) -> Option<String> {
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let anchor = lines.iter().rposition(|l| is_anchor(l))?;
+    lines.insert(anchor + 1, line);
+    Some(lines.join("\n") + "\n")
+}
+
+// The year of a `// YYYY` line heading the days of a year in the registry.
+fn registry_year(line: &str) -> Option<u32> {
+    line.trim().strip_prefix("// ")?.parse().ok()
+}
+
+// The day of a `Box::new(crate::DayNN),` line of the registry.
+fn registry_day(line: &str) -> Option<u8> {
+    let registration = line.trim().strip_prefix("Box::new(")?;
+    let (_, day) = registration.split_once("::Day")?;
+    day.strip_suffix("),")?.parse().ok()
+}
+
+// Inserts `registration` in year and day order among the registered days,
+// under a new heading when it is the first day of its year.
+fn add_registration(contents: &str, year: u32, day: u8, registration: &str) -> Option<String> {
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let start = lines.iter().position(|l| l.trim_end().ends_with("vec!["))? + 1;
+    let end = start + lines[start..].iter().position(|l| l.trim() == "]")?;
+
+    let heading = format!("        // {}", year);
+    match lines[start..end]
+        .iter()
+        .position(|l| registry_year(l) == Some(year))
+    {
+        Some(i) => {
+            let section = start + i + 1;
+            let earlier_days = lines[section..end]
+                .iter()
+                .take_while(|l| registry_day(l).is_some_and(|d| d < day))
+                .count();
+            lines.insert(section + earlier_days, registration);
+        }
+        None => {
+            let later_year = lines[start..end]
+                .iter()
+                .position(|l| registry_year(l).is_some_and(|y| y > year));
+            let position = start + later_year.unwrap_or(end - start);
+            lines.splice(position..position, [heading.as_str(), registration]);
+        }
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+// Appends the day to the `members` array of the workspace manifest.
+fn add_workspace_member(contents: &str, name: &str) -> Option<String> {
+    let members = contents.find("members")?;
+    let end = members + contents[members..].find(']')?;
+    let last = contents[..end].rfind('"')?;
+    let mut contents = contents.to_owned();
+    contents.insert_str(last + 1, &format!(", \"{}\"", name));
+    Some(contents)
+}
+
+// The edited contents of the file at `path`.
+fn edited(
+    path: PathBuf,
+    f: impl FnOnce(&str) -> Option<String>,
+) -> Result<(PathBuf, String), Error> {
+    let contents = std::fs::read_to_string(&path)?;
+    let edited = f(&contents).ok_or_else(|| Error::UnexpectedLayout(path.clone()))?;
+    Ok((path, edited))
+}
+
+fn write_crate(dir: &Path, year: u32, day: u8) -> Result<(), Error> {
+    std::fs::create_dir_all(dir.join("src"))?;
+    std::fs::create_dir_all(dir.join("fixtures"))?;
+    std::fs::write(dir.join("Cargo.toml"), render(CARGO_TEMPLATE, year, day))?;
+    std::fs::write(
+        dir.join("src").join("lib.rs"),
+        render(LIB_TEMPLATE, year, day),
+    )?;
+    std::fs::write(dir.join("fixtures").join("example.txt"), "")?;
+    Ok(())
+}
+
+/// Creates the crate of a day in the workspace at `root`, returning its path.
+/// The days of 2022 are `dayNN` crates at the root, the days of other years
+/// `yYYYY/dayNN` ones.
+///
+/// The workspace files are only edited once the crate is written, so that a
+/// failure leaves the workspace as it was.
+pub fn new_day(root: &Path, year: u32, day: u8) -> Result<PathBuf, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::InvalidDay(day));
+    }
+    let (path, name) = (crate_dir(year, day), crate_name(year, day));
+    let dir = root.join(&path);
+    if dir.exists() {
+        return Err(Error::DayExists(dir));
+    }
+
+    let edits = [
+        edited(root.join("Cargo.toml"), |s| add_workspace_member(s, &path))?,
+        edited(root.join("aoc").join("Cargo.toml"), |s| {
+            let dependency = format!("{} = {{ path = \"../{}\" }}", name, path);
+            insert_after_last(
+                s,
+                |l| l.starts_with("day") || l.starts_with('y'),
+                &dependency,
+            )
+        })?,
+        edited(root.join("aoc").join("src").join("registry.rs"), |s| {
+            let registration = format!(
+                "        Box::new({}::{}),",
+                name,
+                render("{{type}}", year, day)
+            );
+            add_registration(s, year, day, &registration)
+        })?,
+    ];
+
+    if let Err(e) = write_crate(&dir, year, day) {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+    for (path, contents) in edits {
+        std::fs::write(path, contents)?;
+    }
+    Ok(dir)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("invalid day {0}, expected 1 to 25")]
+    InvalidDay(u8),
+    #[error("{} already exists", .0.display())]
+    DayExists(PathBuf),
+    #[error("could not find where to register the new day in {}", .0.display())]
+    UnexpectedLayout(PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A workspace with the 2022 day 9 registered.
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"helpers\", \"aoc\", \"day09\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("aoc").join("Cargo.toml"),
+            "[dependencies]\nhelpers = { path = \"../helpers\" }\nday09 = { path = \"../day09\" }\n\n[dev-dependencies]\ntempfile = \"3\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("aoc").join("src").join("registry.rs"),
+            "    vec![\n        // 2022\n        Box::new(day09::Day09),\n    ]\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    fn read(root: &Path, path: &[&str]) -> String {
+        std::fs::read_to_string(path.iter().fold(root.to_owned(), |p, c| p.join(c))).unwrap()
+    }
+
+    #[test]
+    fn creates_and_registers_a_day() {
+        let dir = workspace();
+        let root = dir.path();
+
+        let day_dir = new_day(root, 2023, 10).unwrap();
+        assert_eq!(day_dir, root.join("y2023").join("day10"));
+
+        assert_eq!(
+            read(root, &["Cargo.toml"]),
+            "[workspace]\nmembers = [\"helpers\", \"aoc\", \"day09\", \"y2023/day10\"]\n"
+        );
+        assert!(read(root, &["aoc", "Cargo.toml"]).contains(
+            "day09 = { path = \"../day09\" }\ny2023_day10 = { path = \"../y2023/day10\" }\n\n"
+        ));
+        assert_eq!(
+            read(root, &["aoc", "src", "registry.rs"]),
+            "    vec![\n        // 2022\n        Box::new(day09::Day09),\n        // 2023\n        Box::new(y2023_day10::Day10),\n    ]\n"
+        );
+
+        let lib = read(root, &["y2023", "day10", "src", "lib.rs"]);
+        assert!(lib.contains(
+            "impl Puzzle for Day10 {\n    const YEAR: u32 = 2023;\n    const DAY: u8 = 10;"
+        ));
+        assert!(!lib.contains("{{"));
+        let manifest = read(root, &["y2023", "day10", "Cargo.toml"]);
+        assert!(manifest.contains("name = \"y2023_day10\""));
+        assert!(manifest.contains("helpers = { path = \"../../helpers\" }"));
+        assert_eq!(
+            read(root, &["y2023", "day10", "fixtures", "example.txt"]),
+            ""
+        );
+
+        assert!(matches!(new_day(root, 2023, 10), Err(Error::DayExists(_))));
+        assert!(matches!(
+            new_day(root, 2023, 26),
+            Err(Error::InvalidDay(26))
+        ));
+    }
+
+    #[test]
+    fn keeps_the_same_day_of_two_years_apart() {
+        let dir = workspace();
+        let root = dir.path();
+
+        let days = [(2023, 1), (2021, 1), (2023, 5), (2022, 1), (2023, 3)];
+        for (year, day) in days {
+            new_day(root, year, day).unwrap();
+        }
+        assert!(root.join("y2021").join("day01").is_dir());
+        assert!(root.join("y2023").join("day01").is_dir());
+        assert!(root.join("day01").is_dir());
+        assert!(!root.join("y2022").exists());
+        assert!(read(root, &["day01", "Cargo.toml"]).contains("name = \"day01\"\n"));
+        assert!(
+            read(root, &["day01", "Cargo.toml"]).contains("helpers = { path = \"../helpers\" }")
+        );
+
+        let registrations = read(root, &["aoc", "src", "registry.rs"])
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with("//") || l.starts_with("Box::new"))
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            registrations,
+            [
+                "// 2021",
+                "Box::new(y2021_day01::Day01),",
+                "// 2022",
+                "Box::new(day01::Day01),",
+                "Box::new(day09::Day09),",
+                "// 2023",
+                "Box::new(y2023_day01::Day01),",
+                "Box::new(y2023_day03::Day03),",
+                "Box::new(y2023_day05::Day05),",
+            ]
+        );
+    }
+
+    #[test]
+    fn template_renders_every_placeholder() {
+        let lib = render(LIB_TEMPLATE, 2022, 7);
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(lib.contains("const YEAR: u32 = 2022;"));
+        let manifest = render(CARGO_TEMPLATE, 2022, 7);
+        assert!(manifest.contains("name = \"day07\""));
+        assert!(!manifest.contains("{{"));
+        let manifest = render(CARGO_TEMPLATE, 2023, 7);
+        assert!(manifest.contains("name = \"y2023_day07\""));
+        assert!(manifest.contains("helpers = { path = \"../../helpers\" }"));
+    }
+
+    #[test]
+    fn leaves_the_workspace_as_it_was_on_failure() {
+        let dir = workspace();
+        let root = dir.path();
+        let files = |root: &Path| {
+            [
+                read(root, &["Cargo.toml"]),
+                read(root, &["aoc", "Cargo.toml"]),
+                read(root, &["aoc", "src", "registry.rs"]),
+            ]
+        };
+        let before = files(root);
+
+        // The crate directory can't be created under a file.
+        std::fs::write(root.join("y2023"), "").unwrap();
+        assert!(matches!(new_day(root, 2023, 10), Err(Error::Io(_))));
+        assert_eq!(files(root), before);
+
+        // The registry isn't laid out as expected.
+        std::fs::write(root.join("aoc").join("src").join("registry.rs"), "").unwrap();
+        assert!(matches!(
+            new_day(root, 2022, 10),
+            Err(Error::UnexpectedLayout(_))
+        ));
+        assert!(!root.join("day10").exists());
+        assert_eq!(read(root, &["Cargo.toml"]), before[0]);
+        assert_eq!(read(root, &["aoc", "Cargo.toml"]), before[1]);
+    }
+}
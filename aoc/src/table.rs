@@ -0,0 +1,59 @@
+/// Plain text table with auto sized, left aligned columns.
+#[derive(Debug, Default, Clone)]
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(header: &[&str]) -> Self {
+        Self {
+            header: header.iter().map(|h| h.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        // Keep multi-line cells, like CRT answers, on a single table row.
+        self.rows
+            .push(row.into_iter().map(|c| c.replace('\n', "\\n")).collect());
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        self.header
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|c| c.chars().count())
+                    .fold(h.chars().count(), std::cmp::max)
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let widths = self.column_widths();
+        let write_row = |f: &mut std::fmt::Formatter<'_>, row: &[String]| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(c, w)| format!("{:w$}", c, w = w))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            writeln!(f, "{}", line.trim_end())
+        };
+
+        write_row(f, &self.header)?;
+        let separator = widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("-+-");
+        writeln!(f, "{}", separator)?;
+        self.rows.iter().try_for_each(|row| write_row(f, row))
+    }
+}
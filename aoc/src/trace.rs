@@ -0,0 +1,270 @@
+//! Timing of the phases of a run, from the tracing spans of the runner, of the
+//! solutions and of their heavy inner phases. The spans are either printed as
+//! a tree with the share of the total time of each one, or written as a
+//! Chrome trace to open in `chrome://tracing` or <https://ui.perfetto.dev>.
+
+use std::{
+    fmt::Write as _,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::{field::Field, span, Subscriber};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trace {
+    Tree,
+    Chrome(PathBuf),
+}
+
+impl From<Option<PathBuf>> for Trace {
+    fn from(path: Option<PathBuf>) -> Self {
+        match path {
+            Some(path) => Trace::Chrome(path),
+            None => Trace::Tree,
+        }
+    }
+}
+
+/// A closed span along with the spans closed within it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanNode {
+    pub name: String,
+    pub elapsed: Duration,
+    pub children: Vec<SpanNode>,
+}
+
+struct Timing {
+    started: Instant,
+    node: SpanNode,
+}
+
+// Appends the fields of a span to its name, like `day day=9`.
+struct FieldsVisitor<'a>(&'a mut String);
+
+impl tracing::field::Visit for FieldsVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        let _ = write!(self.0, " {}={}", field.name(), value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let _ = write!(self.0, " {}={:?}", field.name(), value);
+    }
+}
+
+/// Collects the closed spans into a tree of [`SpanNode`]s.
+#[derive(Debug, Clone, Default)]
+pub struct TreeLayer {
+    roots: Arc<Mutex<Vec<SpanNode>>>,
+}
+
+impl TreeLayer {
+    /// The spans without a parent that closed so far.
+    pub fn roots(&self) -> Vec<SpanNode> {
+        self.roots.lock().unwrap().clone()
+    }
+}
+
+impl<S> Layer<S> for TreeLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut name = attrs.metadata().name().to_owned();
+        attrs.record(&mut FieldsVisitor(&mut name));
+        span.extensions_mut().insert(Timing {
+            started: Instant::now(),
+            node: SpanNode {
+                name,
+                elapsed: Duration::ZERO,
+                children: vec![],
+            },
+        });
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(timing) = span.extensions_mut().remove::<Timing>() else {
+            return;
+        };
+        let node = SpanNode {
+            elapsed: timing.started.elapsed(),
+            ..timing.node
+        };
+        match span.parent() {
+            Some(parent) => {
+                if let Some(parent_timing) = parent.extensions_mut().get_mut::<Timing>() {
+                    parent_timing.node.children.push(node);
+                }
+            }
+            None => self.roots.lock().unwrap().push(node),
+        }
+    }
+}
+
+const BAR_WIDTH: usize = 20;
+
+// Appends the line of each span of the tree, its label being the name of the
+// span after the branches leading to it.
+fn tree_lines<'a>(
+    node: &'a SpanNode,
+    label: String,
+    prefix: &str,
+    lines: &mut Vec<(String, &'a SpanNode)>,
+) {
+    lines.push((label, node));
+    for (i, child) in node.children.iter().enumerate() {
+        let (branch, indent) = match i + 1 == node.children.len() {
+            true => ("└─ ", "   "),
+            false => ("├─ ", "│  "),
+        };
+        let label = format!("{}{}{}", prefix, branch, child.name);
+        tree_lines(child, label, &format!("{}{}", prefix, indent), lines);
+    }
+}
+
+/// Renders the spans as an indented tree, with the time of each span and a
+/// bar of its share of the time of its root span.
+pub fn render_tree(roots: &[SpanNode]) -> String {
+    let mut out = String::new();
+    for root in roots {
+        let mut lines = vec![];
+        tree_lines(root, root.name.clone(), "", &mut lines);
+        let width = lines
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        let total = root.elapsed.as_secs_f64();
+        for (label, node) in lines {
+            let share = match total > 0.0 {
+                true => node.elapsed.as_secs_f64() / total,
+                false => 1.0,
+            };
+            let padding = width - label.chars().count();
+            let _ = writeln!(
+                out,
+                "{}{} {:>12} {:>6.1}% {}",
+                label,
+                " ".repeat(padding),
+                format!("{:.2?}", node.elapsed),
+                share * 100.0,
+                "█".repeat((share * BAR_WIDTH as f64).round() as usize),
+            );
+        }
+    }
+    out
+}
+
+/// Keeps the tracing output alive until the end of the run.
+pub struct TraceGuard {
+    tree: Option<TreeLayer>,
+    _chrome: Option<tracing_chrome::FlushGuard>,
+}
+
+impl TraceGuard {
+    /// The timing tree of the run, when tracing as a tree. A Chrome trace is
+    /// written when the guard is dropped.
+    pub fn finish(self) -> Option<String> {
+        self.tree.map(|tree| render_tree(&tree.roots()))
+    }
+}
+
+/// Installs the global subscriber recording the spans of the run.
+pub fn install(trace: &Trace) -> Result<TraceGuard, Error> {
+    let guard = match trace {
+        Trace::Tree => {
+            let tree = TreeLayer::default();
+            tracing::subscriber::set_global_default(
+                tracing_subscriber::registry().with(tree.clone()),
+            )?;
+            TraceGuard {
+                tree: Some(tree),
+                _chrome: None,
+            }
+        }
+        Trace::Chrome(path) => {
+            let file = std::fs::File::create(path)?;
+            let (chrome, flush) = tracing_chrome::ChromeLayerBuilder::new()
+                .writer(file)
+                .include_args(true)
+                .build();
+            tracing::subscriber::set_global_default(tracing_subscriber::registry().with(chrome))?;
+            TraceGuard {
+                tree: None,
+                _chrome: Some(flush),
+            }
+        }
+    };
+    Ok(guard)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("can't create the trace file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Subscriber(#[from] tracing::subscriber::SetGlobalDefaultError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, millis: u64, children: Vec<SpanNode>) -> SpanNode {
+        SpanNode {
+            name: name.to_owned(),
+            elapsed: Duration::from_millis(millis),
+            children,
+        }
+    }
+
+    #[test]
+    fn collects_nested_spans() {
+        let tree = TreeLayer::default();
+        let subscriber = tracing_subscriber::registry().with(tree.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let _day = tracing::info_span!("day", day = 9).entered();
+            tracing::info_span!("read", input = "input.txt").in_scope(|| {});
+            tracing::info_span!("part", part = 1).in_scope(|| {
+                tracing::info_span!("simulate").in_scope(|| {});
+            });
+        });
+
+        let names = |nodes: &[SpanNode]| nodes.iter().map(|n| n.name.clone()).collect::<Vec<_>>();
+        let roots = tree.roots();
+        assert_eq!(names(&roots), ["day day=9"]);
+        assert_eq!(
+            names(&roots[0].children),
+            ["read input=input.txt", "part part=1"]
+        );
+        assert_eq!(names(&roots[0].children[1].children), ["simulate"]);
+    }
+
+    #[test]
+    fn renders_a_timing_tree() {
+        let root = node(
+            "day",
+            100,
+            vec![
+                node("parse", 50, vec![]),
+                node("part", 25, vec![node("simulate", 25, vec![])]),
+            ],
+        );
+        let lines = render_tree(&[root])
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                format!("day 100.00ms 100.0% {}", "█".repeat(20)),
+                format!("├─ parse 50.00ms 50.0% {}", "█".repeat(10)),
+                format!("└─ part 25.00ms 25.0% {}", "█".repeat(5)),
+                format!("└─ simulate 25.00ms 25.0% {}", "█".repeat(5)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{
+    answers::Answers,
+    input::{self, InputProvider},
+    registry::BoxedSolution,
+    table::Table,
+};
+use color_eyre::eyre::Report;
+use helpers::solution::Part;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum Status {
+    #[display(fmt = "pass")]
+    Pass,
+    #[display(fmt = "FAIL")]
+    Fail,
+    #[display(fmt = "missing")]
+    Missing,
+    #[display(fmt = "ERROR")]
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub expected: Option<String>,
+    // The computed answer, or why there is none.
+    pub actual: String,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Fail | Status::Error)
+    }
+}
+
+pub fn verify(
+    solutions: &[BoxedSolution],
+    inputs: &dyn InputProvider,
+    answers: &Answers,
+) -> Vec<Check> {
+    solutions
+        .iter()
+        .flat_map(|solution| {
+            let (year, day) = (solution.year(), solution.day());
+            let check = |part, actual: String, status| Check {
+                day,
+                part,
+                expected: answers.expected(year, day, part).map(str::to_owned),
+                actual,
+                status,
+            };
+
+            let input = match inputs.fetch(year, day) {
+                Ok(input) => input,
+                Err(input::Error::MissingInput { .. }) => {
+                    return Part::ALL
+                        .iter()
+                        .map(|&part| check(part, "no input".to_owned(), Status::Missing))
+                        .collect::<Vec<_>>()
+                }
+                // A broken store or a failed download fails the run.
+                Err(e) => {
+                    let message = Report::from(e)
+                        .chain()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(": ");
+                    return Part::ALL
+                        .iter()
+                        .map(|&part| check(part, message.clone(), Status::Error))
+                        .collect();
+                }
+            };
+            let outcome = match solution.solve(&input, &Part::ALL) {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    return Part::ALL
+                        .iter()
+                        .map(|&part| check(part, e.to_string(), Status::Error))
+                        .collect()
+                }
+            };
+            outcome
+                .parts
+                .into_iter()
+                .map(|part| match part.answer {
+                    Ok(answer) => {
+                        let status = match answers.matches(year, day, part.part, &answer) {
+                            Some(true) => Status::Pass,
+                            Some(false) => Status::Fail,
+                            None => Status::Missing,
+                        };
+                        check(part.part, answer.to_string(), status)
+                    }
+                    Err(e) => check(part.part, e.to_string(), Status::Error),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn checks_table(checks: &[Check]) -> Table {
+    let mut table = Table::new(&["day", "part", "expected", "actual", "status"]);
+    checks.iter().for_each(|c| {
+        table.add_row(vec![
+            format!("{:02}", c.day),
+            c.part.to_string(),
+            c.expected.clone().unwrap_or_else(|| "-".to_owned()),
+            c.actual.clone(),
+            c.status.to_string(),
+        ])
+    });
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputStore;
+
+    #[test]
+    fn reports_pass_fail_and_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        store
+            .save(2022, 6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n")
+            .unwrap();
+        let answers: Answers = "[2022.day06]\npart1 = 7\npart2 = 20".parse().unwrap();
+        let solutions: Vec<BoxedSolution> = vec![Box::new(day06::Day06), Box::new(day07::Day07)];
+
+        let statuses = verify(&solutions, &store, &answers)
+            .into_iter()
+            .map(|c| (c.day, c.part, c.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                (6, Part::One, Status::Pass),
+                (6, Part::Two, Status::Fail),
+                (7, Part::One, Status::Missing),
+                (7, Part::Two, Status::Missing),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unreadable_inputs_as_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        std::fs::create_dir_all(store.path_for(2022, 6)).unwrap();
+        let solutions: Vec<BoxedSolution> = vec![Box::new(day06::Day06)];
+
+        let checks = verify(&solutions, &store, &Answers::default());
+        assert!(checks
+            .iter()
+            .all(|c| c.status == Status::Error && c.actual != "no input"));
+        assert!(checks.iter().all(Check::is_failure));
+    }
+}
@@ -0,0 +1,169 @@
+//! Re-runs a day whenever its input file changes, comparing the answers with
+//! the ones of the previous run.
+
+use crate::{input, table::Table};
+use helpers::solution::{Answer, Outcome, Part};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Polls an input file for changes of its contents.
+#[derive(Debug)]
+pub struct Watcher {
+    path: PathBuf,
+    contents: Option<String>,
+}
+
+impl Watcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            contents: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The contents of the file if they changed since the last poll, which is
+    /// always the case for the first one.
+    pub fn poll(&mut self) -> Result<Option<&str>, input::Error> {
+        let contents = match input::read_file(&self.path) {
+            Ok(contents) => contents,
+            // Editors that save by replacing the file briefly remove it.
+            Err(input::Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        };
+        if self.contents.as_ref() == Some(&contents) {
+            return Ok(None);
+        }
+        self.contents = Some(contents);
+        Ok(self.contents.as_deref())
+    }
+}
+
+/// What is kept of the outcome of a part, to compare it with the next run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: Part,
+    // The error message when the part failed.
+    pub answer: Result<Answer, String>,
+    pub elapsed: Duration,
+}
+
+impl PartResult {
+    pub fn from_outcome(outcome: &Outcome) -> Vec<PartResult> {
+        outcome
+            .parts
+            .iter()
+            .map(|p| PartResult {
+                part: p.part,
+                answer: p
+                    .answer
+                    .as_ref()
+                    .map(Clone::clone)
+                    .map_err(|e| e.to_string()),
+                elapsed: p.elapsed,
+            })
+            .collect()
+    }
+
+    fn answer_cell(&self) -> String {
+        match self.answer {
+            Ok(ref answer) => answer.to_string(),
+            Err(ref e) => format!("error: {}", e.lines().next().unwrap_or_default()),
+        }
+    }
+}
+
+/// The answers and timings of a run next to the ones of the previous run.
+pub fn changes_table(previous: &[PartResult], current: &[PartResult]) -> Table {
+    let mut table = Table::new(&[
+        "part",
+        "previous",
+        "new",
+        "previous time",
+        "new time",
+        "status",
+    ]);
+    for result in current {
+        let row = match previous.iter().find(|p| p.part == result.part) {
+            Some(before) => vec![
+                before.answer_cell(),
+                result.answer_cell(),
+                format!("{:?}", before.elapsed),
+                format!("{:?}", result.elapsed),
+                match before.answer == result.answer {
+                    true => "same",
+                    false => "CHANGED",
+                }
+                .to_owned(),
+            ],
+            None => vec![
+                "-".to_owned(),
+                result.answer_cell(),
+                "-".to_owned(),
+                format!("{:?}", result.elapsed),
+                "new".to_owned(),
+            ],
+        };
+        table.add_row(
+            std::iter::once(result.part.to_string())
+                .chain(row)
+                .collect(),
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polls_for_changed_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        let mut watcher = Watcher::new(&path);
+        assert_eq!(watcher.poll().unwrap(), None);
+
+        std::fs::write(&path, "R 4\n").unwrap();
+        assert_eq!(watcher.poll().unwrap(), Some("R 4\n"));
+        assert_eq!(watcher.poll().unwrap(), None);
+
+        // Rewriting the same contents is not a change.
+        std::fs::write(&path, "R 4\n").unwrap();
+        assert_eq!(watcher.poll().unwrap(), None);
+        std::fs::write(&path, "R 5\n").unwrap();
+        assert_eq!(watcher.poll().unwrap(), Some("R 5\n"));
+    }
+
+    #[test]
+    fn compares_with_the_previous_run() {
+        let result = |part, answer: Result<i32, &str>| PartResult {
+            part,
+            answer: answer.map(Into::into).map_err(ToOwned::to_owned),
+            elapsed: Duration::from_millis(1),
+        };
+        let previous = [result(Part::One, Ok(13))];
+        let current = [
+            result(Part::One, Ok(13)),
+            result(Part::Two, Err("no solution\nfound")),
+        ];
+
+        let table = changes_table(&previous, &current).to_string();
+        let rows = table
+            .lines()
+            .skip(2)
+            .map(|l| l.split(" | ").map(str::trim).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows[0], ["1", "13", "13", "1ms", "1ms", "same"]);
+        assert_eq!(rows[1], ["2", "-", "error: no solution", "-", "1ms", "new"]);
+
+        let current = [result(Part::One, Ok(14))];
+        let table = changes_table(&previous, &current).to_string();
+        assert!(table.lines().nth(2).unwrap().ends_with("CHANGED"));
+    }
+}
@@ -0,0 +1,27 @@
+use rand::Rng;
+
+/// `size` elves, each carrying a few snacks.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    (0..size.max(1))
+        .map(|_| {
+            let snacks = rng.gen_range(1..=6);
+            (0..snacks)
+                .map(|_| rng.gen_range(1000..=60000).to_string() + "\n")
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day01;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day01, generate, 40, |input| input.split("\n\n").count());
+    }
+}
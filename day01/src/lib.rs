@@ -1,7 +1,12 @@
-use std::{path::Path, str::FromStr};
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
+use helpers::error::{Locate, Result};
+use helpers::solution::{Answer, Puzzle};
 
 #[derive(Debug)]
-struct Elf {
+pub struct Elf {
     snack_calories: Vec<u32>,
 }
 
@@ -10,85 +15,110 @@ impl Elf {
         Elf { snack_calories }
     }
 
-    fn total_calories_carried(&self) -> u32 {
-        self.snack_calories.iter().sum()
-    }
-}
-
-impl FromStr for Elf {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let items = s
-            .lines()
-            .map(|l| l.trim())
-            .filter(|s| !s.is_empty())
-            .map(|s| {
-                s.parse::<u32>()
-                    .map_err(|e| Error::MalformedInput(e, s.to_owned()))
-            })
-            .collect::<Result<Vec<u32>, _>>()?;
-        match items.len() {
-            0 => Err(Error::ElfWithNoSnacks),
-            _ => Ok(Elf::new(items)),
-        }
+    // Summed as u64, so that no count of u32 snacks can overflow it.
+    fn total_calories_carried(&self) -> u64 {
+        self.snack_calories.iter().map(|&c| c as u64).sum()
     }
 }
 
-fn until_err<T, E>(err: &mut &mut Result<(), E>, item: Result<T, E>) -> Option<T> {
-    match item {
-        Ok(item) => Some(item),
-        Err(e) => {
-            **err = Err(e);
-            None
-        }
+fn parse_elf(input: &str, (i, elf): (usize, &str)) -> Result<Elf> {
+    let items = elf
+        .lines()
+        .map(|l| l.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<u32>()
+                .map_err(|e| Error::MalformedInput(e, s.to_owned()))
+                .locate(input, s)
+        })
+        .collect::<Result<Vec<u32>>>()?;
+    match items.len() {
+        0 => Err(Error::ElfWithNoSnacks(i)).locate(input, elf),
+        _ => Ok(Elf::new(items)),
     }
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let mut err = Ok(());
-    let res = std::fs::read_to_string(input)?
-        .split("\n\n")
+fn parse_elfs(s: &str) -> Result<Vec<Elf>> {
+    s.split("\n\n")
         .enumerate()
-        .map(|(i, e)| {
-            e.parse::<Elf>().map_err(|e| {
-                if let Error::ElfWithNoSnacks = e {
-                    Error::ElfWithNoSnacksNumbered(i)
-                } else {
-                    e
-                }
-            })
-        })
-        .scan(&mut err, until_err)
-        .max_by_key(Elf::total_calories_carried);
-    err?;
-    let res = res.ok_or(Error::NoSolution)?.total_calories_carried();
-    println!("p1: {}", res);
-    Ok(())
+        .map(|e| parse_elf(s, e))
+        .collect()
 }
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let mut elfs = std::fs::read_to_string(input)?
-        .split("\n\n")
-        .map(|e| e.parse::<Elf>())
-        .collect::<Result<Vec<Elf>, Error>>()?;
-    elfs.sort_by_cached_key(|e| std::cmp::Reverse(e.total_calories_carried()));
-    let elfs = elfs;
-    let res: u32 = elfs.iter().take(3).map(Elf::total_calories_carried).sum();
-    println!("p2: {}", res);
-    Ok(())
+pub struct Day01;
+
+impl Puzzle for Day01 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 1;
+
+    type Input = Vec<Elf>;
+    type Params = ();
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(parse_elfs(s)?)
+    }
+
+    fn part1(&self, elfs: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let res = elfs
+            .iter()
+            .map(Elf::total_calories_carried)
+            .max()
+            .ok_or(helpers::error::Error::NoSolution)?;
+        Ok(res.into())
+    }
+
+    fn part2(&self, elfs: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let mut calories = elfs
+            .iter()
+            .map(Elf::total_calories_carried)
+            .collect::<Vec<u64>>();
+        calories.sort_by_key(|c| std::cmp::Reverse(*c));
+        let res: u64 = calories.iter().take(3).sum();
+        Ok(res.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error("no solution found")]
-    NoSolution,
     #[error("malformed input: {1}")]
     MalformedInput(#[source] std::num::ParseIntError, String),
-    #[error("malformed input: elf with no snacks (0 calories)")]
-    ElfWithNoSnacks,
     #[error("malformed input: elf {0} with no snacks (0 calories)")]
-    ElfWithNoSnacksNumbered(usize),
+    ElfWithNoSnacks(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations};
+    use helpers::{fixture, solution::Part};
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 24000u64),
+            (fixture!("example.txt"), Part::Two, 45000),
+            ("100\n200\n\n50\n".to_owned(), Part::One, 300),
+            // Fewer than three elves carry everything.
+            ("100\n200\n\n50\n".to_owned(), Part::Two, 350),
+            ("4294967295\n1\n".to_owned(), Part::One, 4294967296),
+        ];
+        assert_answers(&Day01, &cases);
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [
+            ("", (1, 1)),
+            ("1000\n\n\n\n2000", (3, 1)),
+            ("1000\nabc", (2, 1)),
+            ("-5", (1, 1)),
+        ];
+        assert_parse_error_locations(&Day01, &cases);
+    }
+
+    helpers::never_panics!(Day01, generate::generate, 0..50usize);
 }
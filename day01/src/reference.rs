@@ -0,0 +1,42 @@
+//! Reference solutions adding up the calories straight from the text, without
+//! parsing the elves first.
+
+use crate::{generate::generate, Day01};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+
+fn totals(s: &str) -> Vec<u32> {
+    let mut totals = vec![0];
+    for l in s.lines() {
+        match l.trim() {
+            "" => totals.push(0),
+            calories => *totals.last_mut().unwrap() += calories.parse::<u32>().unwrap(),
+        }
+    }
+    totals
+}
+
+pub fn part1(s: &str) -> Answer {
+    totals(s).into_iter().max().unwrap().into()
+}
+
+pub fn part2(s: &str) -> Answer {
+    let mut totals = totals(s);
+    totals.sort();
+    totals.iter().rev().take(3).sum::<u32>().into()
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 24000.into());
+    assert_eq!(part2(&input), 45000.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 1..100usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day01, &input, [part1, part2]);
+    }
+}
@@ -0,0 +1,25 @@
+use rand::seq::SliceRandom;
+
+/// `size` rounds of strategy guide.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    (0..size)
+        .map(|_| {
+            let opponent = ["A", "B", "C"].choose(&mut rng).unwrap();
+            let response = ["X", "Y", "Z"].choose(&mut rng).unwrap();
+            format!("{} {}\n", opponent, response)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day02;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day02, generate, 100, |input| input.lines().count());
+    }
+}
@@ -1,4 +1,9 @@
-use std::path::Path;
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
+use helpers::error::Locate;
+use helpers::solution::{Answer, Puzzle};
 
 const ROCK: u32 = 1;
 const PAPER: u32 = 2;
@@ -8,23 +13,41 @@ const WIN: u32 = 6;
 const DRAW: u32 = 3;
 const LOSS: u32 = 0;
 
+/// The shape played by the opponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opponent {
+    A,
+    B,
+    C,
+}
+
+/// The second column, a shape in part 1 and an outcome in part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+type Round = (Opponent, Response);
+
 /*
 A X - Rock 1p
 B Y - Paper 2p
 C Z - Scissors 3p
 */
-fn round_score_p1(s: &str) -> Result<u32, Error> {
-    match s {
-        "A X" => Ok(ROCK + DRAW),
-        "A Y" => Ok(PAPER + WIN),
-        "A Z" => Ok(SCISSORS + LOSS),
-        "B X" => Ok(ROCK + LOSS),
-        "B Y" => Ok(PAPER + DRAW),
-        "B Z" => Ok(SCISSORS + WIN),
-        "C X" => Ok(ROCK + WIN),
-        "C Y" => Ok(PAPER + LOSS),
-        "C Z" => Ok(SCISSORS + DRAW),
-        _ => Err(Error::InvalidRound(s.to_owned())),
+fn round_score_p1(round: Round) -> u32 {
+    use {Opponent::*, Response::*};
+    match round {
+        (A, X) => ROCK + DRAW,
+        (A, Y) => PAPER + WIN,
+        (A, Z) => SCISSORS + LOSS,
+        (B, X) => ROCK + LOSS,
+        (B, Y) => PAPER + DRAW,
+        (B, Z) => SCISSORS + WIN,
+        (C, X) => ROCK + WIN,
+        (C, Y) => PAPER + LOSS,
+        (C, Z) => SCISSORS + DRAW,
     }
 }
 /*
@@ -32,47 +55,112 @@ A - Rock     X - Lose
 B - Paper    Y - Draw
 C - Scissors Z - Win
 */
-fn round_score_p2(s: &str) -> Result<u32, Error> {
-    match s {
-        "A X" => Ok(LOSS + SCISSORS),
-        "A Y" => Ok(DRAW + ROCK),
-        "A Z" => Ok(WIN + PAPER),
-        "B X" => Ok(LOSS + ROCK),
-        "B Y" => Ok(DRAW + PAPER),
-        "B Z" => Ok(WIN + SCISSORS),
-        "C X" => Ok(LOSS + PAPER),
-        "C Y" => Ok(DRAW + SCISSORS),
-        "C Z" => Ok(WIN + ROCK),
-        _ => Err(Error::InvalidRound(s.to_owned())),
+fn round_score_p2(round: Round) -> u32 {
+    use {Opponent::*, Response::*};
+    match round {
+        (A, X) => LOSS + SCISSORS,
+        (A, Y) => DRAW + ROCK,
+        (A, Z) => WIN + PAPER,
+        (B, X) => LOSS + ROCK,
+        (B, Y) => DRAW + PAPER,
+        (B, Z) => WIN + SCISSORS,
+        (C, X) => LOSS + PAPER,
+        (C, Y) => DRAW + SCISSORS,
+        (C, Z) => WIN + ROCK,
     }
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let res: u32 = std::fs::read_to_string(input)?
-        .lines()
-        .map(round_score_p1)
-        .collect::<Result<Vec<u32>, Error>>()?
-        .iter()
-        .sum();
-    println!("{}", res);
-    Ok(())
+fn parse_round(s: &str, l: &str) -> helpers::error::Result<Round> {
+    let (opponent, response) = l
+        .split_once(' ')
+        .ok_or_else(|| Error::InvalidRound(l.to_owned()))
+        .locate(s, l)?;
+    let opponent = match opponent {
+        "A" => Opponent::A,
+        "B" => Opponent::B,
+        "C" => Opponent::C,
+        _ => return Err(Error::InvalidOpponentMove(opponent.to_owned())).locate(s, opponent),
+    };
+    let response = match response {
+        "X" => Response::X,
+        "Y" => Response::Y,
+        "Z" => Response::Z,
+        _ => return Err(Error::InvalidResponse(response.to_owned())).locate(s, response),
+    };
+    Ok((opponent, response))
 }
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let res: u32 = std::fs::read_to_string(input)?
-        .lines()
-        .filter_map(|l| round_score_p2(l).ok())
-        .sum();
-    println!("{}", res);
-    Ok(())
+fn parse_rounds(s: &str) -> helpers::error::Result<Vec<Round>> {
+    s.lines().map(|l| parse_round(s, l)).collect()
+}
+
+pub struct Day02;
+
+impl Puzzle for Day02 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 2;
+
+    type Input = Vec<Round>;
+    type Params = ();
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(parse_rounds(s)?)
+    }
+
+    fn part1(&self, rounds: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let res = rounds.iter().copied().map(round_score_p1).sum::<u32>();
+        Ok(res.into())
+    }
+
+    fn part2(&self, rounds: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let res = rounds.iter().copied().map(round_score_p2).sum::<u32>();
+        Ok(res.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error("no solution found")]
-    NoSolution,
     #[error("Invalid round move: {0}")]
     InvalidRound(String),
+    #[error("Invalid opponent move {0:?}, expected A, B or C")]
+    InvalidOpponentMove(String),
+    #[error("Invalid response {0:?}, expected X, Y or Z")]
+    InvalidResponse(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations};
+    use helpers::{fixture, solution::Part};
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 15),
+            (fixture!("example.txt"), Part::Two, 12),
+            ("A X\n".to_owned(), Part::One, 4),
+            ("A X\n".to_owned(), Part::Two, 3),
+            ("".to_owned(), Part::One, 0),
+        ];
+        assert_answers(&Day02, &cases);
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [
+            ("A W", (1, 3)),
+            ("D X", (1, 1)),
+            ("AX", (1, 1)),
+            ("A  X", (1, 3)),
+            ("A Y\nB", (2, 1)),
+        ];
+        assert_parse_error_locations(&Day02, &cases);
+    }
+
+    helpers::never_panics!(Day02, generate::generate, 0..50usize);
 }
@@ -0,0 +1,50 @@
+//! Reference solutions scoring rounds with modular arithmetic on the shapes,
+//! instead of a table of every round.
+
+use crate::{generate::generate, Day02};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+
+// Shapes and outcomes as 0, 1 or 2, in the order of the letters.
+fn rounds(s: &str) -> impl Iterator<Item = (u32, u32)> + '_ {
+    s.lines().map(|l| {
+        let b = l.as_bytes();
+        ((b[0] - b'A') as u32, (b[2] - b'X') as u32)
+    })
+}
+
+pub fn part1(s: &str) -> Answer {
+    rounds(s)
+        .map(|(opponent, me)| {
+            // 0 is a draw, 1 a win and 2 a loss.
+            let outcome = (me + 3 - opponent) % 3;
+            me + 1 + [3, 6, 0][outcome as usize]
+        })
+        .sum::<u32>()
+        .into()
+}
+
+pub fn part2(s: &str) -> Answer {
+    rounds(s)
+        .map(|(opponent, outcome)| {
+            let me = (opponent + outcome + 2) % 3;
+            me + 1 + outcome * 3
+        })
+        .sum::<u32>()
+        .into()
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 15.into());
+    assert_eq!(part2(&input), 12.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 0..200usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day02, &input, [part1, part2]);
+    }
+}
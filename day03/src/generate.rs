@@ -0,0 +1,52 @@
+use rand::{seq::SliceRandom, Rng};
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// A rucksack whose compartments share only `misplaced`, and which also holds
+// `badge` in its first compartment. Other items come from `pool`.
+fn rucksack(rng: &mut impl Rng, pool: &[char], misplaced: char, badge: char) -> String {
+    let (first_pool, second_pool) = pool.split_at(pool.len() / 2);
+    let len = rng.gen_range(2..=first_pool.len().min(second_pool.len()) + 1);
+    let mut first = vec![misplaced, badge];
+    first.extend((2..len).map(|_| *first_pool.choose(rng).unwrap()));
+    let mut second = vec![misplaced];
+    second.extend((1..len).map(|_| *second_pool.choose(rng).unwrap()));
+    first.shuffle(rng);
+    second.shuffle(rng);
+    first.into_iter().chain(second).collect()
+}
+
+/// `size` rucksacks, rounded up to whole groups of three elves.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    let groups = size.max(1).div_ceil(3);
+    (0..groups)
+        .flat_map(|_| {
+            let mut items = ITEMS.chars().collect::<Vec<_>>();
+            items.shuffle(&mut rng);
+            // The badge is the only item the three elves have in common, so
+            // every elf gets its own items apart from it.
+            let (badge, items) = items.split_first().unwrap();
+            items
+                .chunks(items.len() / 3)
+                .take(3)
+                .map(|pool| {
+                    let (misplaced, pool) = pool.split_first().unwrap();
+                    rucksack(&mut rng, pool, *misplaced, *badge) + "\n"
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day03;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day03, generate, 30, |input| input.lines().count());
+    }
+}
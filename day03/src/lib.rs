@@ -1,68 +1,139 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
+use helpers::error::Locate;
+use helpers::solution::{Answer, Puzzle};
 use itertools::Itertools;
 use std::collections::HashSet;
-use std::path::Path;
 
+// Items are letters, as checked when parsing.
 fn priority(c: char) -> u32 {
     if c.is_ascii_lowercase() {
         c as u32 - 'a' as u32 + 1
-    } else if c.is_ascii_uppercase() {
-        c as u32 - 'A' as u32 + 27
     } else {
-        unreachable!()
+        c as u32 - 'A' as u32 + 27
     }
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let res: u32 = std::fs::read_to_string(input)?
-        .lines()
-        .enumerate()
-        .map(|(i, rucksack)| {
-            let len = rucksack.len();
-            assert!(len % 2 == 0);
-            let middle = len / 2;
-            let compartment_1 = &rucksack[0..middle].chars().collect::<HashSet<char>>();
-            rucksack[middle..len]
-                .chars()
-                .find(|c| compartment_1.contains(c))
-                .ok_or(Error::NoMisplacedItem(i))
-                .map(priority)
-        })
-        .collect::<Result<Vec<u32>, Error>>()?
-        .iter()
-        .sum();
-    println!("p1: {}", res);
-    Ok(())
+// The items of a rucksack, split in its two compartments.
+type Rucksack = (String, String);
+
+fn parse_rucksack(s: &str, l: &str) -> helpers::error::Result<Rucksack> {
+    if let Some((i, c)) = l.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        return Err(Error::InvalidItem(c)).locate(s, &l[i..i + c.len_utf8()]);
+    }
+    if !l.len().is_multiple_of(2) {
+        return Err(Error::UnevenCompartments(l.len())).locate(s, l);
+    }
+    let (compartment_1, compartment_2) = l.split_at(l.len() / 2);
+    Ok((compartment_1.to_owned(), compartment_2.to_owned()))
 }
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let res: u32 = std::fs::read_to_string(input)?
-        .lines()
-        .tuples()
-        .enumerate()
-        .map(|(i, (e1, e2, e3))| {
-            let collector = |rucksack: &str| rucksack.chars().collect::<HashSet<char>>();
-            let (e1, e2, e3) = (collector(e1), collector(e2), collector(e3));
-            e1.intersection(&e2)
-                .copied()
-                .collect::<HashSet<char>>()
-                .intersection(&e3)
-                .next()
-                .ok_or(Error::NoBadgeFound(i))
-                .map(|item| priority(*item))
-        })
-        .collect::<Result<Vec<u32>, Error>>()?
-        .iter()
-        .sum();
-    println!("p2: {res}");
-    Ok(())
+pub struct Day03;
+
+impl Puzzle for Day03 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 3;
+
+    type Input = Vec<Rucksack>;
+    type Params = ();
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(s.lines()
+            .map(|l| parse_rucksack(s, l))
+            .collect::<helpers::error::Result<_>>()?)
+    }
+
+    fn part1(&self, rucksacks: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let res: u32 = rucksacks
+            .iter()
+            .enumerate()
+            .map(|(i, (compartment_1, compartment_2))| {
+                let compartment_1 = compartment_1.chars().collect::<HashSet<char>>();
+                compartment_2
+                    .chars()
+                    .find(|c| compartment_1.contains(c))
+                    .ok_or(Error::NoMisplacedItem(i))
+                    .map(priority)
+            })
+            .collect::<Result<Vec<u32>, Error>>()?
+            .iter()
+            .sum();
+        Ok(res.into())
+    }
+
+    fn part2(&self, rucksacks: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let res: u32 = rucksacks
+            .iter()
+            .tuples()
+            .enumerate()
+            .map(|(i, (e1, e2, e3))| {
+                let collector =
+                    |(c1, c2): &Rucksack| c1.chars().chain(c2.chars()).collect::<HashSet<char>>();
+                let (e1, e2, e3) = (collector(e1), collector(e2), collector(e3));
+                e1.intersection(&e2)
+                    .copied()
+                    .collect::<HashSet<char>>()
+                    .intersection(&e3)
+                    .next()
+                    .ok_or(Error::NoBadgeFound(i))
+                    .map(|item| priority(*item))
+            })
+            .collect::<Result<Vec<u32>, Error>>()?
+            .iter()
+            .sum();
+        Ok(res.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
+    #[error("Invalid item {0:?}, expected a letter")]
+    InvalidItem(char),
+    #[error("Rucksack with {0} items can't be split in two equal compartments")]
+    UnevenCompartments(usize),
     #[error("no misplaced item in rucksack {0}")]
     NoMisplacedItem(usize),
     #[error("no badge found in group {0}")]
     NoBadgeFound(usize),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations, solve};
+    use helpers::{fixture, solution::Part};
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 157),
+            (fixture!("example.txt"), Part::Two, 70),
+            ("abca\n".to_owned(), Part::One, 1),
+            ("aZbZ\n".to_owned(), Part::One, 52),
+            ("ab\nbc\nbd\n".to_owned(), Part::Two, 2),
+        ];
+        assert_answers(&Day03, &cases);
+    }
+
+    #[test]
+    fn unsolvable_inputs() {
+        let cases = [("abcd", Part::One), ("ab\ncd\nef", Part::Two)];
+        for (input, part) in cases {
+            assert!(solve(&Day03, input, part).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [("abca\nab1b", (2, 3)), ("abc", (1, 1)), ("ab cd", (1, 3))];
+        assert_parse_error_locations(&Day03, &cases);
+    }
+
+    helpers::never_panics!(Day03, generate::generate, 0..50usize);
+}
@@ -0,0 +1,53 @@
+//! Reference solutions looking for shared items letter by letter in the text
+//! of each compartment or rucksack, without sets.
+
+use crate::{generate::generate, Day03};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn priority(c: char) -> u32 {
+    ITEMS.find(c).unwrap() as u32 + 1
+}
+
+pub fn part1(s: &str) -> Answer {
+    s.lines()
+        .map(|l| {
+            let (first, second) = l.split_at(l.len() / 2);
+            let shared = first.chars().find(|&c| second.contains(c)).unwrap();
+            priority(shared)
+        })
+        .sum::<u32>()
+        .into()
+}
+
+pub fn part2(s: &str) -> Answer {
+    let lines = s.lines().collect::<Vec<_>>();
+    lines
+        .chunks(3)
+        .map(|group| {
+            let badge = ITEMS
+                .chars()
+                .find(|&c| group.iter().all(|l| l.contains(c)))
+                .unwrap();
+            priority(badge)
+        })
+        .sum::<u32>()
+        .into()
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 157.into());
+    assert_eq!(part2(&input), 70.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 1..100usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day03, &input, [part1, part2]);
+    }
+}
@@ -0,0 +1,27 @@
+use rand::Rng;
+
+fn range(rng: &mut impl Rng) -> String {
+    let lo = rng.gen_range(1..=99);
+    let hi = rng.gen_range(lo..=99);
+    format!("{}-{}", lo, hi)
+}
+
+/// `size` pairs of section assignments.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    (0..size)
+        .map(|_| format!("{},{}\n", range(&mut rng), range(&mut rng)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day04;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day04, generate, 100, |input| input.lines().count());
+    }
+}
@@ -1,17 +1,28 @@
-use std::{ops::RangeInclusive, path::Path};
+pub mod generate;
+#[cfg(test)]
+mod reference;
 
-type Pair = (RangeInclusive<u8>, RangeInclusive<u8>);
+use helpers::error::{Locate, Result};
+use helpers::solution::{Answer, Puzzle};
+use std::ops::RangeInclusive;
 
-fn parse_pair((i, s): (usize, &str)) -> Result<Pair, Error> {
-    s.split_once(',')
-        .ok_or(Error::InvalidPair(i))
-        .and_then(|(l, r)| Ok((parse_range("left", l)?, parse_range("right", r)?)))
+pub type Pair = (RangeInclusive<u8>, RangeInclusive<u8>);
+
+fn parse_pair(s: &str, l: &str) -> Result<Pair> {
+    let (left, right) = l.split_once(',').ok_or(Error::InvalidPair).locate(s, l)?;
+    Ok((
+        parse_range(s, "left", left)?,
+        parse_range(s, "right", right)?,
+    ))
 }
 
-fn parse_range(side: &'static str, s: &str) -> Result<RangeInclusive<u8>, Error> {
-    s.split_once('-')
+fn parse_range(s: &str, side: &'static str, range: &str) -> Result<RangeInclusive<u8>> {
+    let (lo, hi) = range
+        .split_once('-')
         .ok_or(Error::InvalidRange(side))
-        .and_then(|(lo, hi)| Ok(RangeInclusive::new(lo.parse::<u8>()?, hi.parse::<u8>()?)))
+        .locate(s, range)?;
+    let parse_bound = |bound: &str| bound.parse::<u8>().map_err(Error::from).locate(s, bound);
+    Ok(RangeInclusive::new(parse_bound(lo)?, parse_bound(hi)?))
 }
 
 fn overlaps_fully((left, right): &Pair) -> bool {
@@ -26,40 +37,75 @@ fn overlaps_partially((left, right): &Pair) -> bool {
         || right.contains(left.end())
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let res = std::fs::read_to_string(input)?
-        .lines()
-        .enumerate()
-        .map(parse_pair)
-        .collect::<Result<Vec<Pair>, Error>>()?
-        .iter()
-        .filter(|p| overlaps_fully(p))
-        .count();
-    println!("p1: {res}");
-    Ok(())
-}
+pub struct Day04;
+
+impl Puzzle for Day04 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 4;
+
+    type Input = Vec<Pair>;
+    type Params = ();
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(s.lines()
+            .map(|l| parse_pair(s, l))
+            .collect::<Result<Vec<Pair>>>()?)
+    }
+
+    fn part1(&self, pairs: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let res = pairs.iter().filter(|p| overlaps_fully(p)).count();
+        Ok(res.into())
+    }
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let res = std::fs::read_to_string(input)?
-        .lines()
-        .enumerate()
-        .map(parse_pair)
-        .collect::<Result<Vec<Pair>, Error>>()?
-        .iter()
-        .filter(|p| overlaps_partially(p))
-        .count();
-    println!("p2: {res}");
-    Ok(())
+    fn part2(&self, pairs: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let res = pairs.iter().filter(|p| overlaps_partially(p)).count();
+        Ok(res.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error(transparent)]
+    #[error("Invalid bound")]
     InvalidBound(#[from] std::num::ParseIntError),
-    #[error("Invalid range {0}")]
+    #[error("Invalid {0} range, expected two bounds separated by '-'")]
     InvalidRange(&'static str),
-    #[error("Invalid pair {0}")]
-    InvalidPair(usize),
+    #[error("Invalid pair, expected two ranges separated by ','")]
+    InvalidPair,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations};
+    use helpers::{fixture, solution::Part};
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 2),
+            (fixture!("example.txt"), Part::Two, 4),
+            ("1-1,1-1\n".to_owned(), Part::One, 1),
+            ("1-1,1-1\n".to_owned(), Part::Two, 1),
+            ("1-2,3-4\n".to_owned(), Part::Two, 0),
+        ];
+        assert_answers(&Day04, &cases);
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [
+            ("1-2", (1, 1)),
+            ("1-2,3", (1, 5)),
+            ("1-1,1-1\na-2,3-4", (2, 1)),
+            ("1-300,1-2", (1, 3)),
+            ("1-2;3-4", (1, 1)),
+        ];
+        assert_parse_error_locations(&Day04, &cases);
+    }
+
+    helpers::never_panics!(Day04, generate::generate, 0..50usize);
 }
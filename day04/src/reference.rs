@@ -0,0 +1,48 @@
+//! Reference solutions comparing the sets of every section of both elves,
+//! instead of the bounds of their ranges.
+
+use crate::{generate::generate, Day04};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+// The sections of both elves of each pair.
+fn pairs(s: &str) -> impl Iterator<Item = (HashSet<u32>, HashSet<u32>)> + '_ {
+    let sections = |range: &str| {
+        let (lo, hi) = range.split_once('-').unwrap();
+        (lo.parse().unwrap()..=hi.parse().unwrap()).collect::<HashSet<u32>>()
+    };
+    s.lines().map(move |l| {
+        let (left, right) = l.split_once(',').unwrap();
+        (sections(left), sections(right))
+    })
+}
+
+pub fn part1(s: &str) -> Answer {
+    pairs(s)
+        .filter(|(left, right)| left.is_subset(right) || right.is_subset(left))
+        .count()
+        .into()
+}
+
+pub fn part2(s: &str) -> Answer {
+    pairs(s)
+        .filter(|(left, right)| !left.is_disjoint(right))
+        .count()
+        .into()
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 2.into());
+    assert_eq!(part2(&input), 4.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 0..100usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day04, &input, [part1, part2]);
+    }
+}
@@ -0,0 +1,76 @@
+use rand::Rng;
+
+const STACK_COUNT: usize = 9;
+
+fn drawing(stacks: &[Vec<char>]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_owned(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    lines.push(
+        (1..=stacks.len())
+            .map(|id| format!(" {} ", id))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines.join("\n")
+}
+
+/// A drawing of nine stacks of crates followed by `size` moves. Moves never
+/// take the last crate of a stack, so every stack has a crate on top at the
+/// end.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    let mut stacks = (0..STACK_COUNT)
+        .map(|_| {
+            (0..rng.gen_range(2..=8))
+                .map(|_| rng.gen_range(b'A'..=b'Z') as char)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let drawing = drawing(&stacks);
+
+    let moves = (0..size)
+        .map(|_| {
+            // Not all stacks can be left with a single crate, so there is
+            // always one to move from.
+            let from = loop {
+                let from = rng.gen_range(0..STACK_COUNT);
+                if stacks[from].len() > 1 {
+                    break from;
+                }
+            };
+            let to = (from + rng.gen_range(1..STACK_COUNT)) % STACK_COUNT;
+            let count = rng.gen_range(1..stacks[from].len());
+            let split = stacks[from].len() - count;
+            let moved = stacks[from].split_off(split);
+            stacks[to].extend(moved);
+            format!("move {} from {} to {}\n", count, from + 1, to + 1)
+        })
+        .collect::<String>();
+    format!("{}\n\n{}", drawing, moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day05;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day05, generate, 50, |input| {
+            input.lines().filter(|l| l.starts_with("move")).count()
+        });
+    }
+}
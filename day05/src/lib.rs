@@ -1,62 +1,71 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
+use helpers::error::Locate;
+use helpers::solution::{Answer, Puzzle};
 use itertools::Itertools;
-use std::path::Path;
 
-type Stacks = Vec<Vec<char>>;
-type Op = (usize, usize, usize);
-type Ops = Vec<Op>;
+pub type Stacks = Vec<Vec<char>>;
+pub type Op = (usize, usize, usize);
+pub type Ops = Vec<Op>;
 
-fn parse_input(input: &Path) -> Result<(Stacks, Ops), Error> {
-    let s = std::fs::read_to_string(input)?;
+fn parse_input(s: &str) -> helpers::error::Result<(Stacks, Ops)> {
     let (stacks_str, ops_str) = s
         .split_once("\n\n")
-        .ok_or(Error::StacksAndProcedureDelimiterNotFound)?;
+        .ok_or(Error::StacksAndProcedureDelimiterNotFound)
+        .locate(s, &s[s.len()..])?;
 
-    let stacks = parse_stacks(stacks_str)?;
-    let ops = parse_ops(ops_str)?;
+    let stacks = parse_stacks(s, stacks_str)?;
+    let ops = parse_ops(s, ops_str)?;
     Ok((stacks, ops))
 }
 
-fn parse_stacks(stacks_str: &str) -> Result<Stacks, Error> {
+fn parse_stacks(s: &str, stacks_str: &str) -> helpers::error::Result<Stacks> {
     let mut lines = stacks_str.split('\n').rev();
 
     let ids_char_count = lines
         .next()
         .map(|e| e.len())
-        .ok_or(Error::CantDetermineStackCount)?;
+        .ok_or(Error::CantDetermineStackCount)
+        .locate(s, stacks_str)?;
     let stack_count = ids_char_count / 4 + 1;
 
-    let stacks: Stacks = vec![vec![]; stack_count];
-    let stacks = lines.fold(stacks, |stacks, l| {
-        l.as_bytes()
-            .chunks(4)
-            .enumerate()
-            .filter_map(|(col, e)| {
-                if e[0] == b'[' {
-                    Some((col, e[1] as char))
-                } else {
-                    None
-                }
-            })
-            .fold(stacks, |mut stacks, (col, item)| {
-                stacks[col].push(item);
-                stacks
-            })
-    });
+    let mut stacks: Stacks = vec![vec![]; stack_count];
+    for l in lines {
+        for (col, e) in l.as_bytes().chunks(4).enumerate() {
+            if e[0] != b'[' {
+                continue;
+            }
+            let crate_str = &l[col * 4..];
+            let item = e.get(1).ok_or(Error::MissingCrate).locate(s, crate_str)?;
+            stacks
+                .get_mut(col)
+                .ok_or(Error::CrateOutsideStacks(col + 1))
+                .locate(s, crate_str)?
+                .push(*item as char);
+        }
+    }
 
     Ok(stacks)
 }
 
-fn parse_ops(ops_str: &str) -> Result<Ops, Error> {
-    let ops = ops_str
+fn parse_op(s: &str, l: &str) -> helpers::error::Result<Op> {
+    let (_, count, _, from, _, to) = l
+        .split(' ')
+        .collect_tuple()
+        .ok_or(Error::InvalidOp)
+        .locate(s, l)?;
+    let parse_number = |n: &str| n.parse::<usize>().map_err(Error::from).locate(s, n);
+    Ok((parse_number(count)?, parse_number(from)?, parse_number(to)?))
+}
+
+fn parse_ops(input: &str, ops_str: &str) -> helpers::error::Result<Ops> {
+    ops_str
         .split('\n')
         .filter(|l| !l.is_empty())
-        .map(|l| {
-            let (_, count, _, from, _, to) =
-                l.split(' ').collect_tuple().ok_or(Error::InvalidOp)?;
-            Ok((count.parse()?, from.parse()?, to.parse()?))
-        })
-        .collect::<Result<Ops, Error>>()?;
-    Ok(ops)
+        .map(|l| parse_op(input, l))
+        .collect()
 }
 
 #[allow(unused)]
@@ -71,6 +80,9 @@ fn until_err<T, E>(err: &mut &mut Result<(), E>, item: Result<T, E>) -> Option<T
 }
 
 fn get_two_mut<T>(slice: &mut [T], index1: usize, index2: usize) -> Option<(&mut T, &mut T)> {
+    if index1.max(index2) >= slice.len() {
+        return None;
+    }
     match index1.cmp(&index2) {
         std::cmp::Ordering::Greater => {
             let (start, end) = slice.split_at_mut(index1);
@@ -84,13 +96,14 @@ fn get_two_mut<T>(slice: &mut [T], index1: usize, index2: usize) -> Option<(&mut
     }
 }
 
-fn run_ops(ops: Ops, stacks: &mut Stacks, p2: bool) -> Result<(), Error> {
-    ops.into_iter().try_for_each(|(count, from, to)| {
-        let (stack_from, stack_to) =
-            get_two_mut(stacks, from - 1, to - 1).ok_or(Error::InvalidOp)?;
+#[tracing::instrument(skip(ops, stacks), fields(ops = ops.len()))]
+fn run_ops(ops: &Ops, stacks: &mut Stacks, p2: bool) -> Result<(), Error> {
+    ops.iter().try_for_each(|&(count, from, to)| {
+        let stack_index = |id: usize| id.checked_sub(1).ok_or(Error::NoSuchStack(id));
+        let (stack_from, stack_to) = get_two_mut(stacks, stack_index(from)?, stack_index(to)?)
+            .ok_or(Error::InvalidMove(from, to))?;
 
         let extender = (0..count)
-            .into_iter()
             .map(|_| {
                 let item = stack_from.pop().ok_or(Error::StackEmpty(from))?;
                 Ok(item)
@@ -117,25 +130,47 @@ fn get_stacks_top(stacks: &Stacks) -> Result<String, Error> {
     Ok(res)
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let (mut stacks, ops) = parse_input(input)?;
-    run_ops(ops, &mut stacks, false)?;
-    println!("p1: {}", get_stacks_top(&stacks)?);
-    Ok(())
-}
+pub struct Day05;
+
+impl Puzzle for Day05 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 5;
+
+    type Input = (Stacks, Ops);
+    type Params = ();
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(parse_input(s)?)
+    }
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let (mut stacks, ops) = parse_input(input)?;
-    run_ops(ops, &mut stacks, true)?;
-    println!("p2: {}", get_stacks_top(&stacks)?);
-    Ok(())
+    fn part1(
+        &self,
+        (stacks, ops): &Self::Input,
+        _: &Self::Params,
+    ) -> color_eyre::eyre::Result<Answer> {
+        let mut stacks = stacks.clone();
+        run_ops(ops, &mut stacks, false)?;
+        Ok(get_stacks_top(&stacks)?.into())
+    }
+
+    fn part2(
+        &self,
+        (stacks, ops): &Self::Input,
+        _: &Self::Params,
+    ) -> color_eyre::eyre::Result<Answer> {
+        let mut stacks = stacks.clone();
+        run_ops(ops, &mut stacks, true)?;
+        Ok(get_stacks_top(&stacks)?.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error(transparent)]
+    #[error("Invalid operation number")]
     InvalidOpNumber(#[from] std::num::ParseIntError),
     #[error("Could not find stacks and procedure delimiter not found")]
     StacksAndProcedureDelimiterNotFound,
@@ -145,4 +180,70 @@ pub enum Error {
     InvalidOp,
     #[error("No more elements in stack {0}")]
     StackEmpty(usize),
+    #[error("Crate without a name")]
+    MissingCrate,
+    #[error("Crate in column {0}, past the last stack")]
+    CrateOutsideStacks(usize),
+    #[error("No stack {0}, stacks are numbered from 1")]
+    NoSuchStack(usize),
+    #[error("Can't move crates from stack {0} to stack {1}")]
+    InvalidMove(usize, usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations, solve};
+    use helpers::{fixture, solution::Part};
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, "CMZ"),
+            (fixture!("example.txt"), Part::Two, "MCD"),
+            ("[A]\n 1 \n\n".to_owned(), Part::One, "A"),
+            (
+                "    [C]\n    [B]\n[A] [D]\n 1   2 \n\nmove 2 from 2 to 1\n".to_owned(),
+                Part::One,
+                "BD",
+            ),
+            (
+                "    [C]\n    [B]\n[A] [D]\n 1   2 \n\nmove 2 from 2 to 1\n".to_owned(),
+                Part::Two,
+                "CD",
+            ),
+        ];
+        assert_answers(&Day05, &cases);
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [
+            ("[A]\n 1 ", (2, 4)),
+            ("[A]\n 1 \n\nmove x from 1 to 1", (4, 6)),
+            ("[A]\n 1 \n\nmove 1 from 1 to", (4, 1)),
+            ("[A]\n 1 \n\nmove 1", (4, 1)),
+            ("[A] [B]\n 1 \n\n", (1, 5)),
+            ("[\n 1 \n\n", (1, 1)),
+        ];
+        assert_parse_error_locations(&Day05, &cases);
+    }
+
+    #[test]
+    fn invalid_procedures() {
+        let cases = [
+            // Not enough crates to move.
+            "    [B]\n[A] [C]\n 1   2 \n\nmove 2 from 1 to 2",
+            // Moving onto the same stack.
+            "[A]\n 1 \n\nmove 1 from 1 to 1",
+            // Stacks are numbered from 1.
+            "[A]\n 1 \n\nmove 1 from 0 to 1",
+            "[A]\n 1 \n\nmove 1 from 1 to 2",
+        ];
+        for input in cases {
+            assert!(solve(&Day05, input, Part::One).is_err(), "{input:?}");
+        }
+    }
+
+    helpers::never_panics!(Day05, generate::generate, 0..50usize);
 }
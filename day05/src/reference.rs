@@ -0,0 +1,65 @@
+//! Reference solutions moving crates one at a time, through a second stack
+//! for the CrateMover 9001.
+
+use crate::{generate::generate, Day05};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+
+fn rearrange(s: &str, one_at_a_time: bool) -> Answer {
+    let (drawing, moves) = s.split_once("\n\n").unwrap();
+    let mut stacks: Vec<Vec<u8>> = vec![];
+    for l in drawing.lines().rev().skip(1) {
+        for (i, c) in l.bytes().enumerate().filter(|(i, _)| i % 4 == 1) {
+            if stacks.len() <= i / 4 {
+                stacks.resize(i / 4 + 1, vec![]);
+            }
+            if c != b' ' {
+                stacks[i / 4].push(c);
+            }
+        }
+    }
+
+    for l in moves.lines() {
+        let n = l
+            .split(' ')
+            .filter_map(|w| w.parse::<usize>().ok())
+            .collect::<Vec<_>>();
+        let (count, from, to) = (n[0], n[1] - 1, n[2] - 1);
+        let mut crane = vec![];
+        for _ in 0..count {
+            let c = stacks[from].pop().unwrap();
+            match one_at_a_time {
+                true => stacks[to].push(c),
+                false => crane.push(c),
+            }
+        }
+        while let Some(c) = crane.pop() {
+            stacks[to].push(c);
+        }
+    }
+    let tops = stacks.iter().map(|s| *s.last().unwrap()).collect();
+    String::from_utf8(tops).unwrap().into()
+}
+
+pub fn part1(s: &str) -> Answer {
+    rearrange(s, true)
+}
+
+pub fn part2(s: &str) -> Answer {
+    rearrange(s, false)
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), "CMZ".into());
+    assert_eq!(part2(&input), "MCD".into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 0..100usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day05, &input, [part1, part2]);
+    }
+}
@@ -0,0 +1,32 @@
+use rand::{seq::SliceRandom, Rng};
+
+const MARKER_LEN: usize = 14;
+
+/// A datastream of `size` characters. Characters come from a small alphabet,
+/// so markers are rare, and one start-of-message marker is planted at a
+/// random position.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    let size = size.max(MARKER_LEN);
+    let mut datastream = (0..size)
+        .map(|_| rng.gen_range(b'a'..=b'f'))
+        .collect::<Vec<_>>();
+
+    let mut marker = (b'a'..=b'z').collect::<Vec<_>>();
+    marker.shuffle(&mut rng);
+    let start = rng.gen_range(0..=size - MARKER_LEN);
+    datastream[start..start + MARKER_LEN].copy_from_slice(&marker[..MARKER_LEN]);
+    String::from_utf8(datastream).unwrap() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day06;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day06, generate, 200, |input| input.trim().len());
+    }
+}
@@ -1,41 +1,91 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
+use helpers::error::{Locate, Result};
+use helpers::params::PerPart;
+use helpers::solution::{Answer, Part, Puzzle};
 use itertools::Itertools;
-use std::path::Path;
 
-fn find_marker(s: &str, window_size: usize) -> Result<usize, Error> {
+helpers::params! {
+    pub struct Params {
+        /// Distinct characters in the start-of-packet marker of part 1 and in
+        /// the start-of-message marker of part 2.
+        marker_len: PerPart<usize> = PerPart::new(4, 14),
+    }
+}
+
+fn marker_len(params: &Params, part: Part) -> std::result::Result<usize, Error> {
+    match *params.marker_len.get(part) {
+        0 => Err(Error::EmptyMarker),
+        len => Ok(len),
+    }
+}
+
+fn find_marker(s: &str, window_size: usize) -> Result<usize> {
     s.trim()
         .as_bytes()
         .windows(window_size)
         .position(|w| w.iter().all_unique())
-        .ok_or(Error::NoSolution)
+        .ok_or(helpers::error::Error::NoSolution)
         .map(|e| e + window_size)
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let res = find_marker(&std::fs::read_to_string(input)?, 4)?;
-    println!("p1: {}", res);
-    Ok(())
+fn parse_datastream(s: &str) -> Result<String> {
+    let datastream = s.trim();
+    match datastream
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_lowercase())
+    {
+        Some((i, c)) => Err(Error::InvalidCharacter(c)).locate(s, &datastream[i..i + c.len_utf8()]),
+        None => Ok(datastream.to_owned()),
+    }
 }
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let res = find_marker(&std::fs::read_to_string(input)?, 14)?;
-    println!("p2: {}", res);
-    Ok(())
+pub struct Day06;
+
+impl Puzzle for Day06 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type Params = Params;
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(parse_datastream(s)?)
+    }
+
+    fn part1(&self, datastream: &Self::Input, params: &Params) -> color_eyre::eyre::Result<Answer> {
+        let res = find_marker(datastream, marker_len(params, Part::One)?)?;
+        Ok(res.into())
+    }
+
+    fn part2(&self, datastream: &Self::Input, params: &Params) -> color_eyre::eyre::Result<Answer> {
+        let res = find_marker(datastream, marker_len(params, Part::Two)?)?;
+        Ok(res.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error("no solution found")]
-    NoSolution,
+    #[error("Invalid character {0:?}, expected a lowercase letter")]
+    InvalidCharacter(char),
+    #[error("Markers need at least one character")]
+    EmptyMarker,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations, solve, solve_with};
+    use helpers::{fixture, solution::Part};
 
     #[test]
-    fn test() -> Result<(), Error> {
+    fn test() -> Result<()> {
         let s = "bvwbjplbgvbhsrlpgdmjqwftvncz";
         assert_eq!(find_marker(s, 4)?, 5);
         assert_eq!(find_marker(s, 14)?, 23);
@@ -45,4 +95,52 @@ mod tests {
         assert_eq!(find_marker(s, 14)?, 26);
         Ok(())
     }
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 7),
+            (fixture!("example.txt"), Part::Two, 19),
+            ("nppdvjthqldpwncqszvftbrmjlhg\n".to_owned(), Part::One, 6),
+            (
+                "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_owned(),
+                Part::Two,
+                29,
+            ),
+        ];
+        assert_answers(&Day06, &cases);
+    }
+
+    #[test]
+    fn no_marker() {
+        let cases = [
+            ("", Part::One),
+            ("abab", Part::One),
+            ("abcdefghijklm", Part::Two),
+        ];
+        for (input, part) in cases {
+            assert!(solve(&Day06, input, part).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn custom_marker_lengths() {
+        let input = fixture!("example.txt");
+        let params = |part1, part2| Params {
+            marker_len: PerPart::new(part1, part2),
+        };
+        let solve = |part, params| solve_with(&Day06, &input, part, &params);
+        assert_eq!(solve(Part::One, params(1, 3)).unwrap(), 1.into());
+        assert_eq!(solve(Part::Two, params(1, 3)).unwrap(), 3.into());
+        assert!(solve(Part::One, params(0, 3)).is_err());
+        assert!(solve(Part::Two, params(4, 27)).is_err());
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [("abcD", (1, 4)), ("\nab cd\n", (2, 3))];
+        assert_parse_error_locations(&Day06, &cases);
+    }
+
+    helpers::never_panics!(Day06, generate::generate, 0..100usize);
 }
@@ -0,0 +1,38 @@
+//! Reference solutions building a new set for every window of the
+//! datastream.
+
+use crate::{generate::generate, Day06};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+fn marker_end(s: &str, len: usize) -> Answer {
+    let chars = s.trim().chars().collect::<Vec<_>>();
+    (len..=chars.len())
+        .find(|&end| chars[end - len..end].iter().collect::<HashSet<_>>().len() == len)
+        .unwrap()
+        .into()
+}
+
+pub fn part1(s: &str) -> Answer {
+    marker_end(s, 4)
+}
+
+pub fn part2(s: &str) -> Answer {
+    marker_end(s, 14)
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 7.into());
+    assert_eq!(part2(&input), 19.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 14..500usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day06, &input, [part1, part2]);
+    }
+}
@@ -0,0 +1,78 @@
+use rand::{seq::SliceRandom, Rng};
+
+const NAMES: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
+const EXTENSIONS: [&str; 4] = ["", ".txt", ".dat", ".log"];
+
+struct Dir {
+    name: String,
+    files: Vec<(usize, String)>,
+    dirs: Vec<usize>,
+}
+
+fn transcript(dirs: &[Dir], idx: usize, out: &mut String) {
+    let dir = &dirs[idx];
+    out.push_str("$ ls\n");
+    dir.dirs
+        .iter()
+        .for_each(|&child| out.push_str(&format!("dir {}\n", dirs[child].name)));
+    dir.files
+        .iter()
+        .for_each(|(size, name)| out.push_str(&format!("{} {}\n", size, name)));
+    for &child in &dir.dirs {
+        out.push_str(&format!("$ cd {}\n", dirs[child].name));
+        transcript(dirs, child, out);
+        out.push_str("$ cd ..\n");
+    }
+}
+
+/// A terminal transcript exploring a random file system of `size` files and
+/// directories. Total used space stays below the disk size.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    let max_file_size = (60_000_000 / size.max(1)).clamp(1, 300_000);
+    let mut dirs = vec![Dir {
+        name: "/".to_owned(),
+        files: vec![],
+        dirs: vec![],
+    }];
+    for i in 1..size {
+        // Suffixed with the entry number, so names are unique in each directory.
+        let name = format!("{}{}", NAMES.choose(&mut rng).unwrap(), i);
+        let parent = rng.gen_range(0..dirs.len());
+        match rng.gen_bool(0.3) {
+            true => {
+                let idx = dirs.len();
+                dirs[parent].dirs.push(idx);
+                dirs.push(Dir {
+                    name,
+                    files: vec![],
+                    dirs: vec![],
+                });
+            }
+            false => {
+                let name = name + EXTENSIONS.choose(&mut rng).unwrap();
+                let file_size = rng.gen_range(1..=max_file_size);
+                dirs[parent].files.push((file_size, name));
+            }
+        }
+    }
+
+    let mut out = "$ cd /\n".to_owned();
+    transcript(&dirs, 0, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day07;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        // The root directory is the only one that isn't listed.
+        assert_generates_solvable_inputs(&Day07, generate, 200, |input| {
+            input.lines().filter(|l| !l.starts_with('$')).count() + 1
+        });
+    }
+}
@@ -1,9 +1,25 @@
 extern crate derive_more;
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
 use derive_more::Display;
+use helpers::error::Locate;
+use helpers::solution::{Answer, Puzzle};
 use itertools::Itertools;
-use std::path::Path;
 use tailsome::IntoResult;
 
+helpers::params! {
+    pub struct Params {
+        /// Directories smaller than this size are summed in part 1.
+        small_dir_limit: usize = 100_000,
+        /// Size of the disk.
+        total_space: usize = 70_000_000,
+        /// Free space needed for the update in part 2.
+        needed_space: usize = 30_000_000,
+    }
+}
+
 #[derive(Debug, Display)]
 #[display(fmt = "{} (file, size={})", name, size)]
 struct File {
@@ -60,20 +76,23 @@ impl Directory {
             .cloned()
     }
 
-    fn get_size(&self, fs: &FSArena) -> usize {
+    fn get_size(&self, fs: &FSArena) -> Result<usize, Error> {
         if let Some(size) = self.size.get() {
-            return size;
+            return Ok(size);
         }
 
         let size = fs
             .iter_dir_contents(self)
-            .map(|(fs_entry, _)| match fs_entry {
-                FSEntry::File(file) => file.size,
-                FSEntry::Directory(directory) => directory.get_size(fs),
-            })
-            .sum();
+            .try_fold(0usize, |size, (fs_entry, _)| {
+                let entry_size = match fs_entry {
+                    FSEntry::File(file) => file.size,
+                    FSEntry::Directory(directory) => directory.get_size(fs)?,
+                };
+                size.checked_add(entry_size)
+                    .ok_or_else(|| Error::DirTooLarge(self.name.to_owned()))
+            })?;
         self.size.set(Some(size));
-        size
+        Ok(size)
     }
 }
 
@@ -102,7 +121,7 @@ impl FSEntry {
 }
 
 #[derive(Debug)]
-struct FSArena {
+pub struct FSArena {
     entries: Vec<FSEntry>,
 }
 
@@ -129,7 +148,7 @@ impl FSArena {
             })
     }
 
-    fn iter(&self) -> FSIter {
+    fn iter(&self) -> FSIter<'_> {
         FSIter {
             to_visit: vec![(self.root_index(), 0)],
             fs: self,
@@ -137,7 +156,7 @@ impl FSArena {
         }
     }
 
-    fn iter_dir_contents(&self, dir: &Directory) -> FSIter {
+    fn iter_dir_contents(&self, dir: &Directory) -> FSIter<'_> {
         FSIter {
             to_visit: dir.children.iter().map(|e| (*e, 0)).collect(),
             fs: self,
@@ -145,7 +164,7 @@ impl FSArena {
         }
     }
 
-    fn dir_iter(&self) -> DirectoryIter {
+    fn dir_iter(&self) -> DirectoryIter<'_> {
         DirectoryIter {
             to_visit: vec![self.root_index()],
             fs: self,
@@ -249,7 +268,8 @@ enum ParsedEntry {
     ListFSEntry(FSEntry),
 }
 
-type ParsedEntries = Vec<ParsedEntry>;
+// Parsed entries, along with the input line they were parsed from.
+type ParsedEntries<'a> = Vec<(&'a str, ParsedEntry)>;
 
 fn parse_op(l: &str) -> Result<Op, Error> {
     match l.split(' ').collect::<Vec<_>>().as_slice() {
@@ -274,17 +294,37 @@ fn parse_fs_entry(l: &str) -> Result<FSEntry, Error> {
     .into_ok()
 }
 
-fn parse_ops_and_fs_entries(s: &str) -> Result<ParsedEntries, Error> {
-    s.split('\n')
+fn parse_entry(l: &str) -> Result<ParsedEntry, Error> {
+    match &l.get(0..1) {
+        Some("$") => ParsedEntry::DoOp(parse_op(l)?),
+        Some(_) => ParsedEntry::ListFSEntry(parse_fs_entry(l)?),
+        None => Error::InvalidLine(l.to_owned()).into_err()?,
+    }
+    .into_ok()
+}
+
+// The part of the line an error should point at.
+fn offending_text<'a>(l: &'a str, e: &Error) -> &'a str {
+    match e {
+        Error::InvalidFileSize(_) => l.split(' ').next().unwrap_or(l),
+        Error::FSEntryNotFound(name) | Error::DuplicateEntry(name)
+            if l.ends_with(name.as_str()) =>
+        {
+            &l[l.len() - name.len()..]
+        }
+        _ => l,
+    }
+}
+
+#[tracing::instrument(skip_all)]
+fn parse_ops_and_fs_entries(s: &str) -> helpers::error::Result<ParsedEntries<'_>> {
+    s.lines()
         .map(|l| {
-            match &l.get(0..1) {
-                Some("$") => ParsedEntry::DoOp(parse_op(l)?),
-                Some(_) => ParsedEntry::ListFSEntry(parse_fs_entry(l)?),
-                None => Error::InvalidLine(l.to_owned()).into_err()?,
-            }
-            .into_ok()
+            parse_entry(l)
+                .map(|entry| (l, entry))
+                .map_err(|e| helpers::error::Error::input(s, offending_text(l, &e), e))
         })
-        .collect::<Result<Vec<ParsedEntry>, Error>>()
+        .collect()
 }
 
 struct AssembleFSState {
@@ -298,6 +338,9 @@ fn handle_parsed_entry(
 ) -> Result<AssembleFSState, Error> {
     let curr_dir_idx = &mut state.curr_dir_idx;
     let fs = &mut state.fs;
+    if fs.entries.is_empty() && !matches!(entry, ParsedEntry::DoOp(Op::ChangeDirectoryRoot)) {
+        return Err(Error::NoRootDirectoryInFS);
+    }
     match entry {
         ParsedEntry::DoOp(Op::ChangeDirectory(directory)) => {
             let curr_dir = &fs[*curr_dir_idx];
@@ -317,6 +360,9 @@ fn handle_parsed_entry(
                 _ => Error::CurrentDirIsNotDir(curr_dir_idx.0).into_err()?,
             }
         }
+        ParsedEntry::DoOp(Op::ChangeDirectoryRoot) if !fs.entries.is_empty() => {
+            *curr_dir_idx = fs.root_index();
+        }
         ParsedEntry::DoOp(Op::ChangeDirectoryRoot) => {
             *curr_dir_idx = fs.next_index();
             let mut directory = Directory::new("/");
@@ -329,7 +375,9 @@ fn handle_parsed_entry(
         }
         ParsedEntry::ListFSEntry(FSEntry::File(file)) => {
             let curr_dir = &fs[*curr_dir_idx];
-            assert!(curr_dir.find_child_idx_by_name(&file.name, fs).is_none());
+            if curr_dir.find_child_idx_by_name(&file.name, fs).is_some() {
+                return Err(Error::DuplicateEntry(file.name));
+            }
             let new_file = FSEntry::File(file);
             let new_file_idx = fs.next_index();
             fs.entries.push(new_file);
@@ -343,9 +391,12 @@ fn handle_parsed_entry(
         }
         ParsedEntry::ListFSEntry(FSEntry::Directory(directory)) => {
             let curr_dir = &fs[*curr_dir_idx];
-            assert!(curr_dir
+            if curr_dir
                 .find_child_idx_by_name(&directory.name, fs)
-                .is_none());
+                .is_some()
+            {
+                return Err(Error::DuplicateEntry(directory.name));
+            }
             let mut directory = directory;
             let new_dir_idx = fs.next_index();
             directory.parent = Some(*curr_dir_idx);
@@ -364,66 +415,87 @@ fn handle_parsed_entry(
     Ok(state)
 }
 
-fn assemble_fs(entries: ParsedEntries) -> Result<FSArena, Error> {
+#[tracing::instrument(skip_all, fields(entries = entries.len()))]
+fn assemble_fs(s: &str, entries: ParsedEntries) -> helpers::error::Result<FSArena> {
     let state = AssembleFSState {
         curr_dir_idx: FSEntryIdx::new(0),
         fs: FSArena::new(),
     };
-    entries
+    let fs = entries
         .into_iter()
-        .try_fold(state, |state, e| handle_parsed_entry(e, state))
-        .map(|state| state.fs)
+        .try_fold(state, |state, (l, e)| {
+            handle_parsed_entry(e, state)
+                .map_err(|e| helpers::error::Error::input(s, offending_text(l, &e), e))
+        })?
+        .fs;
+    fs.root_directory().locate(s, &s[s.len()..])?;
+    Ok(fs)
 }
 
-fn sum_dirs_with_size_lt_100k(fs: &FSArena) -> usize {
+#[tracing::instrument(skip_all)]
+fn sum_small_dirs(fs: &FSArena, params: &Params) -> Result<usize, Error> {
     fs.dir_iter()
         .map(|dir| dir.get_size(fs))
-        .filter(|size| *size < 100_000)
-        .sum()
+        .filter_ok(|size| *size < params.small_dir_limit)
+        .try_fold(0usize, |sum, size| {
+            sum.checked_add(size?).ok_or(Error::SumTooLarge)
+        })
 }
 
-fn find_smallest_dir_to_del(fs: &FSArena) -> Result<usize, Error> {
-    let total_space: usize = 70_000_000;
-    let needed_space: usize = 30_000_000;
-    let used_space = fs.root_directory()?.get_size(fs);
+#[tracing::instrument(skip_all)]
+fn find_smallest_dir_to_del(fs: &FSArena, params: &Params) -> Result<Option<usize>, Error> {
+    let used_space = fs.root_directory()?.get_size(fs)?;
+    let free_space = params
+        .total_space
+        .checked_sub(used_space)
+        .ok_or(Error::NotEnoughTotalSpace(used_space))?;
     fs.dir_iter()
         .map(|dir| dir.get_size(fs))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
         .sorted()
-        .find(|size| (total_space - used_space + size) > needed_space)
-        .ok_or(Error::NoSolution)
+        .find(|size| (free_space + size) > params.needed_space)
+        .into_ok()
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let s = std::fs::read_to_string(input)?;
-    let entries = parse_ops_and_fs_entries(&s)?;
-    let fs = assemble_fs(entries)?;
-    let res = sum_dirs_with_size_lt_100k(&fs);
-    println!("p1: {}", res);
-    Ok(())
-}
+pub struct Day07;
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let s = std::fs::read_to_string(input)?;
-    let entries = parse_ops_and_fs_entries(&s)?;
-    let fs = assemble_fs(entries)?;
-    let res = find_smallest_dir_to_del(&fs)?;
-    println!("p2: {}", res);
-    Ok(())
+impl Puzzle for Day07 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 7;
+
+    type Input = FSArena;
+    type Params = Params;
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        let entries = parse_ops_and_fs_entries(s)?;
+        Ok(assemble_fs(s, entries)?)
+    }
+
+    fn part1(&self, fs: &Self::Input, params: &Params) -> color_eyre::eyre::Result<Answer> {
+        let res = sum_small_dirs(fs, params)?;
+        Ok(res.into())
+    }
+
+    fn part2(&self, fs: &Self::Input, params: &Params) -> color_eyre::eyre::Result<Answer> {
+        let res = find_smallest_dir_to_del(fs, params)?.ok_or(helpers::error::Error::NoSolution)?;
+        Ok(res.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error("no solution found")]
-    NoSolution,
     #[error("Invalid operation {0}")]
     InvalidOperation(String),
     #[error("Invalid file system entry {0}")]
     InvalidFSEntry(String),
     #[error("Failed to parse line {0}")]
     InvalidLine(String),
-    #[error(transparent)]
+    #[error("Invalid file size")]
     InvalidFileSize(#[from] std::num::ParseIntError),
     #[error("No root directory found")]
     NoRootDirectoryInFS,
@@ -433,4 +505,76 @@ pub enum Error {
     DirHasNoParent(String),
     #[error("PWD is not a directory {0}")]
     CurrentDirIsNotDir(usize),
+    #[error("Duplicate file system entry {0}")]
+    DuplicateEntry(String),
+    #[error("Size of directory {0} overflows")]
+    DirTooLarge(String),
+    #[error("Sum of directory sizes overflows")]
+    SumTooLarge,
+    #[error("Used space {0} is larger than the total space")]
+    NotEnoughTotalSpace(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations, solve, solve_with};
+    use helpers::{fixture, solution::Part};
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 95437),
+            (fixture!("example.txt"), Part::Two, 24933642),
+            ("$ cd /\n$ ls\n100 a\n".to_owned(), Part::One, 100),
+            // Already enough free space, so the smallest directory is picked.
+            (
+                "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n5 b\n".to_owned(),
+                Part::Two,
+                5,
+            ),
+        ];
+        assert_answers(&Day07, &cases);
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [
+            ("$ cd /\n$ rm a", (2, 1)),
+            ("$ cd /\n$ ls\nabc a", (3, 1)),
+            ("$ cd /\n$ ls\n1 a b", (3, 1)),
+            ("$ cd /\n$ cd missing", (2, 6)),
+            ("$ cd /\n$ cd ..", (2, 1)),
+            ("$ cd /\n$ ls\n1 a\ndir a", (4, 5)),
+            ("$ ls\n$ cd /", (1, 1)),
+            ("", (1, 1)),
+        ];
+        assert_parse_error_locations(&Day07, &cases);
+    }
+
+    #[test]
+    fn custom_disk() {
+        let input = fixture!("example.txt");
+        let params = Params {
+            small_dir_limit: 600,
+            total_space: 50_000_000,
+            ..Params::default()
+        };
+        let solve = |part| solve_with(&Day07, &input, part, &params).unwrap();
+        assert_eq!(solve(Part::One), 584.into());
+        assert_eq!(solve(Part::Two), 48381165.into());
+    }
+
+    #[test]
+    fn unsolvable_inputs() {
+        let cases = [
+            ("$ cd /\n$ ls\n70000001 a", Part::Two),
+            ("$ cd /\n$ ls\n18446744073709551615 a\n1 b", Part::One),
+        ];
+        for (input, part) in cases {
+            assert!(solve(&Day07, input, part).is_err(), "{input:?}");
+        }
+    }
+
+    helpers::never_panics!(Day07, generate::generate, 0..50usize);
 }
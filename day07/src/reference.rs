@@ -0,0 +1,71 @@
+//! Reference solutions keeping the path of every file, and summing all the
+//! files below each directory over and over.
+
+use crate::{generate::generate, Day07};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+
+// The size of every directory, summing all files below it over and over.
+fn dir_sizes(s: &str) -> Vec<usize> {
+    let mut cwd: Vec<&str> = vec![];
+    let mut dirs = vec![vec![]];
+    let mut files = vec![];
+    for l in s.lines() {
+        match l.split(' ').collect::<Vec<_>>().as_slice() {
+            ["$", "cd", "/"] => cwd.clear(),
+            ["$", "cd", ".."] => {
+                cwd.pop();
+            }
+            ["$", "cd", dir] => {
+                cwd.push(dir);
+                dirs.push(cwd.clone());
+            }
+            ["$", "ls"] | ["dir", _] => {}
+            [size, _] => files.push((cwd.clone(), size.parse::<usize>().unwrap())),
+            _ => panic!("unexpected line {}", l),
+        }
+    }
+    dirs.iter()
+        .map(|dir| {
+            files
+                .iter()
+                .filter(|(path, _)| path.starts_with(dir))
+                .map(|(_, size)| size)
+                .sum()
+        })
+        .collect()
+}
+
+pub fn part1(s: &str) -> Answer {
+    dir_sizes(s)
+        .into_iter()
+        .filter(|&size| size < 100_000)
+        .sum::<usize>()
+        .into()
+}
+
+pub fn part2(s: &str) -> Answer {
+    let sizes = dir_sizes(s);
+    let free = 70_000_000 - sizes[0];
+    sizes
+        .into_iter()
+        .filter(|&size| free + size > 30_000_000)
+        .min()
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 95437.into());
+    assert_eq!(part2(&input), 24933642.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 1..300usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day07, &input, [part1, part2]);
+    }
+}
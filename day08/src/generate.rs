@@ -0,0 +1,29 @@
+use rand::Rng;
+
+/// A forest of `size` rows of trees, and between half and twice as many
+/// columns.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    let rows = size.max(1);
+    let cols = rng.gen_range(rows.div_ceil(2)..=rows * 2);
+    (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| char::from_digit(rng.gen_range(0..10), 10).unwrap())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day08;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day08, generate, 30, |input| input.lines().count());
+    }
+}
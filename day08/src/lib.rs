@@ -1,18 +1,21 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
 use helpers::grid::{Grid, GridIterDirection, GridPos};
-use std::path::Path;
-use tailsome::IntoResult;
+use helpers::solution::{Answer, Puzzle};
 
 type Height = i8;
 const TREE_VISIBLE: Height = -1;
-type HeightMap = Grid<Height>;
+pub type HeightMap = Grid<Height>;
 
-struct Forest {
-    heightmap: HeightMap,
+struct Forest<'a> {
+    heightmap: &'a HeightMap,
     visibility_grids: Vec<HeightMap>,
 }
 
-impl Forest {
-    fn new(heightmap: HeightMap) -> Self {
+impl<'a> Forest<'a> {
+    fn new(heightmap: &'a HeightMap) -> Self {
         let rows = heightmap.rows;
         let cols = heightmap.cols;
         Forest {
@@ -21,6 +24,7 @@ impl Forest {
         }
     }
 
+    #[tracing::instrument(skip_all)]
     fn compute_visibility_grid_from_each_direction(&mut self) {
         enum_iterator::all::<GridIterDirection>().for_each(|direction| {
             let grid_iter = self.heightmap.grid_pos_iter(&direction);
@@ -36,14 +40,12 @@ impl Forest {
     }
 
     fn is_tree_visible(&self, pos: GridPos) -> bool {
-        (0..self.visibility_grids.len())
-            .into_iter()
-            .map(|direction_index| {
-                self.heightmap[pos] > self.visibility_grids[direction_index][pos]
-            })
-            .any(|visible| visible)
+        (0..self.visibility_grids.len()).any(|direction_index| {
+            self.heightmap[pos] > self.visibility_grids[direction_index][pos]
+        })
     }
 
+    #[tracing::instrument(skip_all)]
     fn count_visible_trees(&self) -> usize {
         self.heightmap
             .grid_pos_iter(&GridIterDirection::Right)
@@ -57,80 +59,111 @@ impl Forest {
 
         enum_iterator::all::<GridIterDirection>()
             .map(|direction| {
-                let bounds = self.heightmap.bounds_in_direction_of(&direction, &origin);
-
-                // Flag to mark that we need to include the element that is bigger or equal,
-                // once we find one. No fancy itertools iterator exists as of commit time.
-                let mut took_gte_height = false;
-                self.heightmap
-                    .pos_iter_along_axis_with_bounds(origin, bounds, &direction)
-                    .take_while(move |pos| {
-                        if took_gte_height {
-                            return false;
-                        }
-                        if self.heightmap[*pos] >= tree_height {
-                            took_gte_height = true;
-                        }
-                        true
-                    })
-                    .count()
+                // Walk towards the edge, counting trees up to and including
+                // the first one that is at least as tall.
+                let delta = direction.delta();
+                let mut viewing_distance = 0;
+                let mut pos = origin;
+                while let Some(next) = self.heightmap.offset(pos, delta) {
+                    viewing_distance += 1;
+                    if self.heightmap[next] >= tree_height {
+                        break;
+                    }
+                    pos = next;
+                }
+                viewing_distance
             })
             .product()
     }
 
-    fn find_highest_scenic_score(&self) -> usize {
+    #[tracing::instrument(skip_all)]
+    fn find_highest_scenic_score(&self) -> Option<usize> {
         self.heightmap
             .grid_pos_iter(&GridIterDirection::Right)
             .flat_map(|axis_iter| axis_iter.map(|pos| self.get_tree_scenic_score(pos)))
             .max()
-            .expect("At least one tree should have the highest scenic score")
     }
 }
 
-fn parse_grid(s: &str) -> Result<HeightMap, Error> {
-    let row_count = s.split('\n').count();
-
-    s.split('\n')
-        .enumerate()
-        .flat_map(|(row, l)| {
-            l.chars().enumerate().map(move |(col, c)| {
-                c.to_digit(10)
-                    .ok_or(Error::InvalidHeight(c))
-                    .map(|height| (row, col, height as Height))
-            })
-        })
-        .try_fold(Grid::new(row_count, row_count), |mut grid, t| {
-            let (row, col, h) = t?;
-            grid[(row, col).into()] = h;
-            grid.into_ok()
-        })
+fn parse_grid(s: &str) -> helpers::error::Result<HeightMap> {
+    Grid::parse(s, |c| {
+        c.to_digit(10)
+            .map(|height| height as Height)
+            .ok_or(Error::InvalidHeight(c))
+    })
 }
 
-pub fn part1(input: &Path) -> Result<(), Error> {
-    let s = std::fs::read_to_string(input)?;
-    let grid = parse_grid(&s)?;
-    let mut forest = Forest::new(grid);
-    forest.compute_visibility_grid_from_each_direction();
-    let res = forest.count_visible_trees();
-    println!("p1: {}", res);
-    Ok(())
-}
+pub struct Day08;
+
+impl Puzzle for Day08 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 8;
+
+    type Input = HeightMap;
+    type Params = ();
+
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(parse_grid(s)?)
+    }
 
-pub fn part2(input: &Path) -> Result<(), Error> {
-    let s = std::fs::read_to_string(input)?;
-    let grid = parse_grid(&s)?;
-    let forest = Forest::new(grid);
-    let res = forest.find_highest_scenic_score();
-    println!("p2: {}", res);
-    Ok(())
+    fn part1(&self, heightmap: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let mut forest = Forest::new(heightmap);
+        forest.compute_visibility_grid_from_each_direction();
+        let res = forest.count_visible_trees();
+        Ok(res.into())
+    }
+
+    fn part2(&self, heightmap: &Self::Input, _: &Self::Params) -> color_eyre::eyre::Result<Answer> {
+        let forest = Forest::new(heightmap);
+        let res = forest
+            .find_highest_scenic_score()
+            .ok_or(helpers::error::Error::NoSolution)?;
+        Ok(res.into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error("no solution found")]
-    NoSolution,
     #[error("Invalid height {0}")]
     InvalidHeight(char),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations};
+    use helpers::{fixture, solution::Part};
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 21),
+            (fixture!("example.txt"), Part::Two, 8),
+            ("5".to_owned(), Part::One, 1),
+            ("11\n11".to_owned(), Part::One, 4),
+            ("111\n191\n111".to_owned(), Part::Two, 1),
+            ("123\n456\n".to_owned(), Part::One, 6),
+            ("19\n91\n11".to_owned(), Part::One, 6),
+            ("2221\n2921\n2221".to_owned(), Part::Two, 2),
+        ];
+        assert_answers(&Day08, &cases);
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [
+            ("12\n3x", (2, 2)),
+            ("1 2\n34", (1, 2)),
+            ("12\n345", (2, 3)),
+            ("123\n45\n678", (2, 1)),
+            ("", (1, 1)),
+        ];
+        assert_parse_error_locations(&Day08, &cases);
+    }
+
+    helpers::never_panics!(Day08, generate::generate, 0..20usize);
+}
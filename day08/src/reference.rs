@@ -0,0 +1,71 @@
+//! Reference solutions collecting the trees in each direction from every
+//! tree, where part 1 of the real ones sweeps the grid once per direction.
+
+use crate::{generate::generate, Day08};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+
+fn heights(s: &str) -> Vec<Vec<u8>> {
+    s.lines()
+        .map(|l| l.bytes().map(|b| b - b'0').collect())
+        .collect()
+}
+
+// The trees seen from the tree at `(row, col)` in each direction, nearest
+// first.
+fn lines_of_sight(h: &[Vec<u8>], row: usize, col: usize) -> [Vec<u8>; 4] {
+    [
+        (0..row).rev().map(|r| h[r][col]).collect(),
+        (row + 1..h.len()).map(|r| h[r][col]).collect(),
+        (0..col).rev().map(|c| h[row][c]).collect(),
+        (col + 1..h[row].len()).map(|c| h[row][c]).collect(),
+    ]
+}
+
+fn trees(h: &[Vec<u8>]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..h.len()).flat_map(move |row| (0..h[row].len()).map(move |col| (row, col)))
+}
+
+pub fn part1(s: &str) -> Answer {
+    let h = heights(s);
+    trees(&h)
+        .filter(|&(row, col)| {
+            lines_of_sight(&h, row, col)
+                .iter()
+                .any(|line| line.iter().all(|&t| t < h[row][col]))
+        })
+        .count()
+        .into()
+}
+
+pub fn part2(s: &str) -> Answer {
+    let h = heights(s);
+    trees(&h)
+        .map(|(row, col)| {
+            lines_of_sight(&h, row, col)
+                .iter()
+                .map(|line| match line.iter().position(|&t| t >= h[row][col]) {
+                    Some(blocked_at) => blocked_at + 1,
+                    None => line.len(),
+                })
+                .product::<usize>()
+        })
+        .max()
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn reference_solves_example() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 21.into());
+    assert_eq!(part2(&input), 8.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 1..30usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day08, &input, [part1, part2]);
+    }
+}
@@ -0,0 +1,24 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// `size` head motions.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = helpers::generate::rng(seed);
+    (0..size)
+        .map(|_| {
+            let direction = ["U", "R", "D", "L"].choose(&mut rng).unwrap();
+            format!("{} {}\n", direction, rng.gen_range(1..=20))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day09;
+    use helpers::fixture::assert_generates_solvable_inputs;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        assert_generates_solvable_inputs(&Day09, generate, 100, |input| input.lines().count());
+    }
+}
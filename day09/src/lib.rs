@@ -1,18 +1,35 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
 use eframe::egui;
-use error_stack::{IntoReport, Report, Result as ESResult, ResultExt};
-use helpers::grid::{
-    Direction9, Grid, GridExtents, GridIterDirection, GridPos, GridPosDelta, GridPosISize,
-};
+use error_stack::{IntoReport, Result as ESResult, ResultExt};
+use helpers::error::Locate;
+use helpers::grid::{Direction9, GridIterDirection, GridPosDelta, GridPosISize, SparseGrid};
+use helpers::params::PerPart;
+use helpers::solution::{Answer, Part, Puzzle};
 use itertools::Itertools;
-use std::{
-    path::Path,
-    str::FromStr,
-    sync::{Arc, Barrier, Mutex},
-};
+use std::sync::{Arc, Barrier, Mutex};
 use tailsome::IntoResult;
 
 type StepCount = usize;
 
+helpers::params! {
+    pub struct Params {
+        /// Knots of the rope in each part, including the head.
+        knots: PerPart<usize> = PerPart::new(2, 10),
+    }
+}
+
+const MAX_KNOTS: usize = 10_000;
+
+fn knot_count(params: &Params, part: Part) -> Result<usize, Error> {
+    match *params.knots.get(part) {
+        knots @ 1..=MAX_KNOTS => Ok(knots),
+        knots => Err(Error::InvalidKnotCount(knots)),
+    }
+}
+
 #[derive(Clone, Copy, Debug, derive_more::Display, PartialEq, Eq, Hash)]
 enum OpKind {
     #[display(fmt = "U")]
@@ -27,42 +44,55 @@ enum OpKind {
 
 #[derive(Clone, Copy, Debug, derive_more::Display, PartialEq, Eq, Hash)]
 #[display(fmt = "{} {}", op_kind, step_count)]
-struct Op {
+pub struct Op {
     op_kind: OpKind,
     step_count: StepCount,
 }
 
-impl FromStr for Op {
-    type Err = Report<ParseOpError>;
-
-    fn from_str(l: &str) -> Result<Self, Self::Err> {
-        let make_error = || ParseOpError(l.to_owned());
-        match l.split(' ').collect::<Vec<_>>().as_slice() {
-            [dir, x] => {
-                let step_count = x
-                    .parse::<StepCount>()
-                    .into_report()
-                    .change_context(make_error())?;
-                let op_kind = match *dir {
-                    "U" => OpKind::Up,
-                    "R" => OpKind::Right,
-                    "D" => OpKind::Down,
-                    "L" => OpKind::Left,
-                    _ => make_error().into_err()?,
-                };
-                Op {
-                    op_kind,
-                    step_count,
-                }
-                .into_ok()
-            }
-            _ => make_error().into_err()?,
-        }
+fn parse_op(s: &str, l: &str) -> helpers::error::Result<Op> {
+    let (dir, step_count) = match l.split(' ').collect::<Vec<_>>().as_slice() {
+        [dir, step_count] => (*dir, *step_count),
+        _ => ParseOpError::InvalidOp(l.to_owned())
+            .into_err()
+            .locate(s, l)?,
+    };
+    let op_kind = match dir {
+        "U" => OpKind::Up,
+        "R" => OpKind::Right,
+        "D" => OpKind::Down,
+        "L" => OpKind::Left,
+        _ => ParseOpError::InvalidDirection(dir.to_owned())
+            .into_err()
+            .locate(s, dir)?,
+    };
+    let step_count = step_count
+        .parse::<StepCount>()
+        .map_err(ParseOpError::from)
+        .locate(s, step_count)?;
+    Op {
+        op_kind,
+        step_count,
     }
+    .into_ok()
 }
 
-fn parse_ops(s: &str) -> ESResult<Ops, ParseOpError> {
-    s.lines().map(|l| l.parse()).try_collect()
+// The simulation allocates a single step op per step, so they are bounded.
+const MAX_STEP_COUNT: usize = 1 << 24;
+
+fn parse_ops(s: &str) -> helpers::error::Result<Ops> {
+    let mut total_steps: usize = 0;
+    s.lines()
+        .map(|l| {
+            let op = parse_op(s, l)?;
+            let step_count_str = l.rsplit(' ').next().unwrap_or(l);
+            total_steps = total_steps
+                .checked_add(op.step_count)
+                .filter(|steps| *steps <= MAX_STEP_COUNT)
+                .ok_or(ParseOpError::TooManySteps(MAX_STEP_COUNT))
+                .locate(s, step_count_str)?;
+            Ok(op)
+        })
+        .try_collect()
 }
 
 impl Op {
@@ -83,20 +113,14 @@ impl Op {
         }
     }
 
-    fn move_pos(&self, pos: &mut GridPos) {
-        let dir = self.grid_iter_direction();
-        let adjusted_delta = dir.delta() * self.step_count() as isize;
-        *pos += adjusted_delta;
-    }
-
-    fn move_pos_isize(&self, pos: &mut GridPosISize) {
+    fn move_pos(&self, pos: &mut GridPosISize) {
         let dir = self.grid_iter_direction();
         let adjusted_delta = dir.delta() * self.step_count() as isize;
         *pos += adjusted_delta;
     }
 }
 
-type Ops = Vec<Op>;
+pub type Ops = Vec<Op>;
 
 fn split_ops_in_single_steps(ops: &Ops) -> Ops {
     ops.iter()
@@ -110,6 +134,7 @@ fn split_ops_in_single_steps(ops: &Ops) -> Ops {
         .collect()
 }
 
+#[allow(unused)]
 struct PrettyOps<'a>(&'a Ops);
 
 impl<'a> std::fmt::Display for PrettyOps<'a> {
@@ -118,9 +143,10 @@ impl<'a> std::fmt::Display for PrettyOps<'a> {
     }
 }
 
-#[derive(Clone, Copy, Debug, derive_more::Display, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, derive_more::Display, PartialEq, Eq, Hash)]
 enum Tile {
     #[display(fmt = ".")]
+    #[default]
     Empty,
     #[display(fmt = "s")]
     Start,
@@ -162,35 +188,28 @@ impl PartialOrd for Tile {
     }
 }
 
-impl Default for Tile {
-    fn default() -> Self {
-        Tile::Empty
-    }
-}
-
 #[derive(Debug, derive_more::Display, Clone)]
 #[display(fmt = "{}", grid)]
 struct RopeSimulation {
-    knots: Vec<GridPos>,
-    start: GridPos,
-    grid: Grid<Tile>,
-    visited: Vec<bool>,
+    knots: Vec<GridPosISize>,
+    start: GridPosISize,
+    grid: SparseGrid<Tile>,
+    // Only the visited tiles are written.
+    visited: SparseGrid<bool>,
 }
 
 impl RopeSimulation {
-    fn new(start: GridPos, extents: &GridExtents, knot_count: usize) -> RopeSimulation {
-        let grid = Grid::new(
-            extents.row_range.end as usize,
-            extents.col_range.end as usize,
-        );
-        let visited_len = grid.rows * grid.cols;
+    fn new(knot_count: usize) -> RopeSimulation {
+        let start = GridPosISize::default();
         let mut s = RopeSimulation {
             knots: vec![start; knot_count],
             start,
-            grid,
-            visited: vec![false; visited_len],
+            grid: SparseGrid::new(),
+            visited: SparseGrid::new(),
         };
         s.grid[start] = Tile::Start;
+        // The tail starts on the start tile, even if it never moves.
+        s.visited[start] = true;
         s
     }
 
@@ -202,11 +221,7 @@ impl RopeSimulation {
 
     #[allow(unused)]
     fn reset_grid(&mut self) {
-        (0..self.grid.rows).for_each(|row| {
-            (0..self.grid.cols).for_each(|col| {
-                self.grid[(row, col).into()] = Tile::Empty;
-            });
-        });
+        self.grid.clear();
     }
 
     #[allow(unused)]
@@ -214,14 +229,9 @@ impl RopeSimulation {
         self.reset_grid();
 
         // Add visited nodes to grid, will be overidden later.
-        self.visited
-            .iter()
-            .enumerate()
-            .filter(|(_, e)| **e)
-            .for_each(|(i, _)| {
-                let pos = self.grid.get_pos_from_linear_index(i);
-                self.grid[pos] = Tile::Visited;
-            });
+        self.visited.iter().for_each(|(pos, _)| {
+            self.grid[pos] = Tile::Visited;
+        });
 
         // Add the knots to the grid.
         self.knots
@@ -239,7 +249,7 @@ impl RopeSimulation {
             });
     }
 
-    fn direction_unit_delta(from: &GridPos, to: &GridPos) -> GridPosDelta {
+    fn direction_unit_delta(from: &GridPosISize, to: &GridPosISize) -> GridPosDelta {
         let mut delta = *to - *from;
         if delta.row_delta != 0 {
             delta.row_delta /= delta.row_delta.abs();
@@ -264,19 +274,18 @@ impl RopeSimulation {
         // Adjust tail positions, using the current front knot as a head and the
         // next knot as the tail.
         // Iterate one less knot, because the last knot is never a head.
-        (0..self.knots.len() - 1).for_each(|front_index| {
-            let tail_index = front_index + 1;
+        (1..self.knots.len()).for_each(|tail_index| {
+            let front_index = tail_index - 1;
 
             if !self.is_knots_touching(front_index, tail_index) {
                 self.move_tail_towards_head(front_index, tail_index)
             }
-
-            // If processing the last tail, mark the tile it's on as visited.
-            if tail_index == self.knots.len() - 1 {
-                let linearized_index = self.grid.get_element_index(self.knots[tail_index]);
-                self.visited[linearized_index] = true;
-            }
         });
+
+        // Mark the tile the last tail is on as visited, which is the head
+        // itself for a rope of a single knot.
+        let last_knot = self.knots[self.knots.len() - 1];
+        self.visited[last_knot] = true;
     }
 
     fn simulate_step(&mut self, ops: &Ops, op_index: usize) {
@@ -284,24 +293,18 @@ impl RopeSimulation {
         self.process_op(op);
     }
 
+    #[tracing::instrument(skip_all, fields(knots = self.knots.len(), steps = ops.len()))]
     fn simulate(&mut self, ops: &Ops) {
-        ops.iter().enumerate().for_each(|(_i, op)| {
+        ops.iter().for_each(|op| {
             self.process_op(op);
         });
     }
 
     fn tail_visited_count(&self) -> usize {
-        self.visited.iter().filter(|e| **e).count()
+        self.visited.len()
     }
 }
 
-fn compute_grid_extents(ops: &Ops) -> GridExtents {
-    GridExtents::compute_grid_extents(ops.iter().scan(GridPosISize::default(), |pos, op| {
-        op.move_pos_isize(pos);
-        Some(*pos)
-    }))
-}
-
 #[derive(Clone)]
 struct RopeSimulationState {
     simulation: RopeSimulation,
@@ -309,52 +312,48 @@ struct RopeSimulationState {
     current_op_index: usize,
 }
 
-fn prepare_simulation(s: &str, knot_count: usize) -> ESResult<RopeSimulationState, PuzzleError> {
-    let ops = parse_ops(s).change_context(PuzzleError)?;
-    let extents = compute_grid_extents(&ops);
-    let normalized_extents = extents.normalized();
-    let normalized_origin = extents.normalized_pos(&GridPos::default());
-    let simulation = RopeSimulation::new(normalized_origin, &normalized_extents, knot_count);
-    let ops = split_ops_in_single_steps(&ops);
+#[tracing::instrument(skip(ops))]
+fn prepare_simulation(ops: &Ops, knot_count: usize) -> RopeSimulationState {
+    let simulation = RopeSimulation::new(knot_count);
+    let ops = split_ops_in_single_steps(ops);
     RopeSimulationState {
         simulation,
         ops,
         current_op_index: 0,
     }
-    .into_ok()
 }
 
-fn part_compute(s: &str, knot_count: usize) -> ESResult<usize, PuzzleError> {
-    let state = prepare_simulation(s, knot_count)?;
+fn part_compute(ops: &Ops, knot_count: usize) -> usize {
+    let state = prepare_simulation(ops, knot_count);
     let (mut s, ops) = (state.simulation, state.ops);
     s.simulate(&ops);
-    s.tail_visited_count().into_ok()
+    s.tail_visited_count()
 }
 
-fn part1_compute(s: &str) -> ESResult<usize, PuzzleError> {
-    part_compute(s, 2)
-}
+pub struct Day09;
 
-fn part2_compute(s: &str) -> ESResult<usize, PuzzleError> {
-    part_compute(s, 10)
-}
+impl Puzzle for Day09 {
+    const YEAR: u32 = 2022;
+    const DAY: u8 = 9;
 
-pub fn part1(input: &Path) -> ESResult<(), PuzzleError> {
-    let s = std::fs::read_to_string(input)
-        .into_report()
-        .change_context(PuzzleError)?;
-    let res = part1_compute(&s)?;
-    println!("p1: {}", res);
-    Ok(())
-}
+    type Input = Ops;
+    type Params = Params;
 
-pub fn part2(input: &Path) -> ESResult<(), PuzzleError> {
-    let s = std::fs::read_to_string(input)
-        .into_report()
-        .change_context(PuzzleError)?;
-    let res = part2_compute(&s)?;
-    println!("p2: {}", res);
-    Ok(())
+    fn parse(&self, s: &str) -> color_eyre::eyre::Result<Self::Input> {
+        Ok(parse_ops(s)?)
+    }
+
+    fn part1(&self, ops: &Self::Input, params: &Params) -> color_eyre::eyre::Result<Answer> {
+        Ok(part_compute(ops, knot_count(params, Part::One)?).into())
+    }
+
+    fn part2(&self, ops: &Self::Input, params: &Params) -> color_eyre::eyre::Result<Answer> {
+        Ok(part_compute(ops, knot_count(params, Part::Two)?).into())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::generate(seed, size))
+    }
 }
 
 struct GuiState {
@@ -369,7 +368,8 @@ struct GuiState {
 
 impl GuiState {
     fn try_new(input: &str, knot_count: usize) -> ESResult<Self, PuzzleError> {
-        let state = prepare_simulation(input, knot_count)?;
+        let ops = parse_ops(input).into_report().change_context(PuzzleError)?;
+        let state = prepare_simulation(&ops, knot_count);
 
         Self {
             simulation_state: state.clone(),
@@ -425,11 +425,10 @@ impl MyApp {
     fn update_grid(&mut self, ui: &mut egui::Ui) {
         let state = self.state.lock().unwrap();
 
-        for row in 0..state.simulation_state.simulation.grid.rows {
-            for col in 0..state.simulation_state.simulation.grid.cols {
-                let pos = (row, col).into();
-                let tile = state.simulation_state.simulation.grid[pos];
-                ui.label(format!("{tile}"));
+        let grid = &state.simulation_state.simulation.grid;
+        for row in grid.grid_pos_iter(&GridIterDirection::Right) {
+            for pos in row {
+                ui.label(format!("{}", grid[pos]));
             }
             ui.end_row();
         }
@@ -543,16 +542,35 @@ pub fn run_gui(input: &str) -> ESResult<(), PuzzleError> {
 // }
 
 #[derive(Debug, thiserror::Error)]
-#[error("Failed to parse op: {0}")]
-pub struct ParseOpError(String);
+pub enum ParseOpError {
+    #[error("Failed to parse op: {0}")]
+    InvalidOp(String),
+    #[error("Invalid direction {0:?}, expected U, R, D or L")]
+    InvalidDirection(String),
+    #[error("Invalid step count")]
+    InvalidStepCount(#[from] std::num::ParseIntError),
+    #[error("More than {0} steps in total")]
+    TooManySteps(usize),
+}
 
 #[derive(Debug, thiserror::Error)]
 #[error("Something failed")]
 pub struct PuzzleError;
 
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("A rope needs between 1 and {MAX_KNOTS} knots, not {0}")]
+    InvalidKnotCount(usize),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use helpers::fixture::{assert_answers, assert_parse_error_locations};
+    use helpers::{
+        fixture,
+        solution::{Part, Solution},
+    };
 
     #[test]
     fn test() -> ESResult<(), PuzzleError> {
@@ -564,8 +582,9 @@ R 4
 D 1
 L 5
 R 2";
-        assert_eq!(part1_compute(s)?, 13);
-        assert_eq!(part2_compute(s)?, 1);
+        let ops = parse_ops(s).into_report().change_context(PuzzleError)?;
+        assert_eq!(part_compute(&ops, 2), 13);
+        assert_eq!(part_compute(&ops, 10), 1);
 
         let s = "R 5
 U 8
@@ -575,7 +594,59 @@ R 17
 D 10
 L 25
 U 20";
-        assert_eq!(part2_compute(s)?, 36);
+        let ops = parse_ops(s).into_report().change_context(PuzzleError)?;
+        assert_eq!(part_compute(&ops, 10), 36);
         Ok(())
     }
+
+    #[test]
+    fn examples() {
+        let cases = [
+            (fixture!("example.txt"), Part::One, 13),
+            (fixture!("example.txt"), Part::Two, 1),
+            (fixture!("example2.txt"), Part::One, 88),
+            (fixture!("example2.txt"), Part::Two, 36),
+            ("R 1\n".to_owned(), Part::One, 1),
+            ("L 3\n".to_owned(), Part::One, 3),
+            // Far from the start, on both sides of it.
+            ("R 5000\nU 5000\nL 20000\n".to_owned(), Part::One, 29998),
+            ("R 5000\nU 5000\nL 20000\n".to_owned(), Part::Two, 29974),
+        ];
+        assert_answers(&Day09, &cases);
+    }
+
+    #[test]
+    fn custom_knot_counts() {
+        let solve = |input: &str, knots: &str| {
+            let params = [("knots".to_owned(), knots.to_owned())];
+            let outcome = Day09.solve_with(input, &Part::ALL, &params)?;
+            outcome
+                .parts
+                .into_iter()
+                .map(|p| p.answer)
+                .collect::<color_eyre::eyre::Result<Vec<_>>>()
+        };
+        let input = fixture!("example2.txt");
+        assert_eq!(solve(&input, "2,10").unwrap(), [88.into(), 36.into()]);
+        assert_eq!(solve(&input, "10,2").unwrap(), [36.into(), 88.into()]);
+        // A single knot is the head, visiting every tile it moves over.
+        assert_eq!(solve("R 4\nU 2", "1").unwrap(), [7.into(), 7.into()]);
+        assert!(solve(&input, "0").is_err());
+        assert!(solve(&input, "2,100000").is_err());
+    }
+
+    #[test]
+    fn malformed_inputs() {
+        let cases = [
+            ("X 1", (1, 1)),
+            ("R 1\nR", (2, 1)),
+            ("R x", (1, 3)),
+            ("R -1", (1, 3)),
+            ("R1", (1, 1)),
+            ("R 1\nL 16777216", (2, 3)),
+        ];
+        assert_parse_error_locations(&Day09, &cases);
+    }
+
+    helpers::never_panics!(Day09, generate::generate, 0..50usize);
 }
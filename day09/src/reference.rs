@@ -0,0 +1,57 @@
+//! Reference solutions moving the rope one step at a time, remembering the
+//! tail positions in a set instead of a grid.
+
+use crate::{generate::generate, Day09};
+use helpers::{fixture, fixture::assert_matches_reference, solution::Answer};
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+fn tail_visited(s: &str, knots: usize) -> Answer {
+    let mut rope = vec![(0i64, 0i64); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+    for l in s.lines() {
+        let (dir, steps) = l.split_once(' ').unwrap();
+        let (dx, dy) = match dir {
+            "U" => (0, -1),
+            "D" => (0, 1),
+            "L" => (-1, 0),
+            "R" => (1, 0),
+            _ => panic!("unexpected direction {}", dir),
+        };
+        for _ in 0..steps.parse::<usize>().unwrap() {
+            rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+            for i in 1..knots {
+                let (x, y) = (rope[i - 1].0 - rope[i].0, rope[i - 1].1 - rope[i].1);
+                if x.abs() > 1 || y.abs() > 1 {
+                    rope[i] = (rope[i].0 + x.signum(), rope[i].1 + y.signum());
+                }
+            }
+            visited.insert(rope[knots - 1]);
+        }
+    }
+    visited.len().into()
+}
+
+pub fn part1(s: &str) -> Answer {
+    tail_visited(s, 2)
+}
+
+pub fn part2(s: &str) -> Answer {
+    tail_visited(s, 10)
+}
+
+#[test]
+fn reference_solves_examples() {
+    let input = fixture!("example.txt");
+    assert_eq!(part1(&input), 13.into());
+    assert_eq!(part2(&input), 1.into());
+    assert_eq!(part2(&fixture!("example2.txt")), 36.into());
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(seed: u64, size in 0..60usize) {
+        let input = generate(seed, size);
+        assert_matches_reference(&Day09, &input, [part1, part2]);
+    }
+}
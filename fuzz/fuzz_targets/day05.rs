@@ -0,0 +1,10 @@
+#![no_main]
+
+use helpers::solution::{input_from_utf8, Part, Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = input_from_utf8(data) {
+        let _ = day05::Day05.solve(input, &Part::ALL);
+    }
+});
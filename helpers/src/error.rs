@@ -0,0 +1,227 @@
+//! Errors shared by all days.
+//!
+//! Parsers attach the place in the puzzle input that failed to parse, so that
+//! every day reports malformed input the same way.
+use std::fmt;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A place in the puzzle input. Lines and columns start at 1, like in editors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    // The whole line containing the offending text.
+    pub line_text: String,
+    pub text: String,
+}
+
+impl Location {
+    /// Locates `text` within `input`. `text` is expected to be a slice of
+    /// `input`, otherwise its first occurrence is used, or the start of the
+    /// input if there is none.
+    pub fn of(input: &str, text: &str) -> Self {
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + text.len() <= input.len())
+            .or_else(|| input.find(text))
+            .unwrap_or(0);
+        Self::at_offset(input, offset, text)
+    }
+
+    fn at_offset(input: &str, offset: usize, text: &str) -> Self {
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let line_text = input[line_start..line_end].trim_end_matches('\r');
+        Location {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            line_text: line_text.to_owned(),
+            text: text.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {:?}",
+            self.line, self.column, self.text
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("no solution found")]
+    NoSolution,
+    #[error("malformed input at {location}")]
+    Input {
+        location: Location,
+        #[source]
+        source: BoxError,
+    },
+}
+
+impl Error {
+    pub fn input(input: &str, text: &str, source: impl Into<BoxError>) -> Self {
+        Error::Input {
+            location: Location::of(input, text),
+            source: source.into(),
+        }
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Input { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+}
+
+/// Compiler style rendering of an input error:
+///
+/// ```text
+/// error: invalid height 'x'
+///  --> inputs/2022/day08.txt:2:3
+///   |
+/// 2 | 30x73
+///   |   ^
+/// ```
+pub struct Diagnostic<'a> {
+    pub file_name: &'a str,
+    pub location: &'a Location,
+    pub message: String,
+}
+
+impl Error {
+    /// The diagnostic of input errors, which are the only ones with a location.
+    pub fn diagnostic<'a>(&'a self, file_name: &'a str) -> Option<Diagnostic<'a>> {
+        let Error::Input { location, source } = self else {
+            return None;
+        };
+        let mut message = source.to_string();
+        let mut cause = source.source();
+        while let Some(e) = cause {
+            message = format!("{}: {}", message, e);
+            cause = e.source();
+        }
+        Some(Diagnostic {
+            file_name,
+            location,
+            message,
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Location {
+            line,
+            column,
+            line_text,
+            text,
+        } = self.location;
+        // Text spanning several lines is only underlined up to the end of
+        // the first one, and empty text still gets a single caret.
+        let line_rest = line_text.chars().count().saturating_sub(column - 1);
+        let width = text.chars().count().min(line_rest).max(1);
+        let gutter = " ".repeat(line.to_string().len());
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, self.file_name, line, column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line, line_text)?;
+        writeln!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(column - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+/// Attaches the offending part of the input to parse errors.
+pub trait Locate<T> {
+    fn locate(self, input: &str, text: &str) -> Result<T>;
+}
+
+impl<T, E: Into<BoxError>> Locate<T> for std::result::Result<T, E> {
+    fn locate(self, input: &str, text: &str) -> Result<T> {
+        self.map_err(|e| Error::input(input, text, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_slices_of_the_input() {
+        let input = "1000\n2000\r\n\nA X\n";
+        let location = Location::of(input, &input[14..15]);
+        assert_eq!(location.line, 4);
+        assert_eq!(location.column, 3);
+        assert_eq!(location.line_text, "A X");
+        assert_eq!(location.text, "X");
+
+        let location = Location::of(input, &input[5..9]);
+        assert_eq!((location.line, location.column), (2, 1));
+        assert_eq!(location.line_text, "2000");
+
+        // Not a slice of the input, so it is searched for.
+        assert_eq!(Location::of(input, "A").line, 4);
+        assert_eq!(Location::of(input, "missing").line, 1);
+        // The empty slice at the very end.
+        assert_eq!(Location::of(input, &input[input.len()..]).line, 5);
+    }
+
+    #[test]
+    fn reports_location_and_source() {
+        let input = "R 4\nU x\n";
+        let err = "x".parse::<u8>().locate(input, &input[6..7]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "malformed input at line 2, column 3: \"x\""
+        );
+        assert_eq!(err.location().map(|l| l.column), Some(3));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn renders_diagnostics() {
+        let input = "R 4\nUp 10\n";
+        let err = Err::<(), _>("invalid direction")
+            .locate(input, &input[4..6])
+            .unwrap_err();
+        let diagnostic = err.diagnostic("day09.txt").unwrap();
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: invalid direction\n \
+             --> day09.txt:2:1\n  \
+             |\n\
+             2 | Up 10\n  \
+             | ^^\n"
+        );
+
+        // Missing text at the end of a line.
+        let err = Err::<(), _>("missing step count")
+            .locate(input, &input[3..3])
+            .unwrap_err();
+        let diagnostic = err.diagnostic("day09.txt").unwrap().to_string();
+        assert!(
+            diagnostic.ends_with("1 | R 4\n  |    ^\n"),
+            "{}",
+            diagnostic
+        );
+
+        assert!(Error::NoSolution.diagnostic("day09.txt").is_none());
+    }
+}
@@ -0,0 +1,126 @@
+//! Test fixtures, kept as files in the `fixtures` directory of each crate.
+
+use crate::error::{Error, Location};
+use crate::solution::{Answer, Part, Puzzle};
+use std::path::Path;
+
+/// Loads a fixture of the calling crate, panicking if it does not exist.
+#[macro_export]
+macro_rules! fixture {
+    ($name:expr) => {
+        $crate::fixture::load(env!("CARGO_MANIFEST_DIR"), $name)
+    };
+}
+
+/// A property test that solving mutated generated inputs of the given sizes
+/// never panics, named `never_panics`. The calling crate needs `proptest`.
+#[macro_export]
+macro_rules! never_panics {
+    ($puzzle:expr, $generate:path, $sizes:expr) => {
+        proptest::proptest! {
+            #[test]
+            fn never_panics(seed: u64, size in $sizes, mutation_seed: u64) {
+                let input = $crate::generate::mutate(mutation_seed, &$generate(seed, size));
+                let _ = $crate::fixture::solve_parts(&$puzzle, &input);
+            }
+        }
+    };
+}
+
+pub fn load(manifest_dir: &str, name: &str) -> String {
+    let path = Path::new(manifest_dir).join("fixtures").join(name);
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to load fixture {}: {}", path.display(), e))
+}
+
+// Parses the input and solves a single part of it.
+pub fn solve<P: Puzzle>(puzzle: &P, input: &str, part: Part) -> color_eyre::eyre::Result<Answer> {
+    solve_with(puzzle, input, part, &P::Params::default())
+}
+
+// Same as `solve`, with other parameters than the puzzle ones.
+pub fn solve_with<P: Puzzle>(
+    puzzle: &P,
+    input: &str,
+    part: Part,
+    params: &P::Params,
+) -> color_eyre::eyre::Result<Answer> {
+    let input = puzzle.parse(input)?;
+    puzzle.solve_part(&input, params, part)
+}
+
+// Parses malformed input, returning where the parse error points at.
+pub fn parse_error_location<P: Puzzle>(puzzle: &P, input: &str) -> Option<Location> {
+    let report = puzzle.parse(input).err()?;
+    report.downcast_ref::<Error>()?.location().cloned()
+}
+
+// Solves both parts of the input, which can be malformed.
+pub fn solve_parts<P: Puzzle>(puzzle: &P, input: &str) -> [color_eyre::eyre::Result<Answer>; 2] {
+    Part::ALL.map(|part| solve(puzzle, input, part))
+}
+
+/// Checks the answer of each `(input, part, expected)` case.
+pub fn assert_answers<P, I, A>(puzzle: &P, cases: &[(I, Part, A)])
+where
+    P: Puzzle,
+    I: AsRef<str>,
+    A: Clone + Into<Answer>,
+{
+    for (input, part, expected) in cases {
+        let input = input.as_ref();
+        assert_eq!(
+            solve(puzzle, input, *part).unwrap(),
+            expected.clone().into(),
+            "part {part} of:\n{input}"
+        );
+    }
+}
+
+/// Checks that the parse error of each `(input, (line, column))` case points
+/// at the given place.
+pub fn assert_parse_error_locations<P: Puzzle>(puzzle: &P, cases: &[(&str, (usize, usize))]) {
+    for (input, expected) in cases {
+        let location = parse_error_location(puzzle, input);
+        assert_eq!(
+            location.map(|l| (l.line, l.column)),
+            Some(*expected),
+            "{input:?}"
+        );
+    }
+}
+
+/// Checks that both parts of the input are solved like the reference
+/// solutions do.
+pub fn assert_matches_reference<P: Puzzle>(
+    puzzle: &P,
+    input: &str,
+    reference: [fn(&str) -> Answer; 2],
+) {
+    for (part, reference) in Part::ALL.into_iter().zip(reference) {
+        assert_eq!(
+            solve(puzzle, input, part).unwrap(),
+            reference(input),
+            "part {part} of:\n{input}"
+        );
+    }
+}
+
+/// Checks that `generate` gives the same input for the same seed, of the
+/// size it was asked for as counted by `size_of`, and that both parts of the
+/// input can be solved.
+pub fn assert_generates_solvable_inputs<P: Puzzle>(
+    puzzle: &P,
+    generate: fn(u64, usize) -> String,
+    size: usize,
+    size_of: fn(&str) -> usize,
+) {
+    for seed in 0..20 {
+        let input = generate(seed, size);
+        assert_eq!(input, generate(seed, size), "seed {seed}");
+        assert_eq!(size_of(&input), size, "seed {seed}:\n{input}");
+        for part in Part::ALL {
+            solve(puzzle, &input, part).unwrap();
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Seeded random puzzle inputs, for stress and property tests.
+
+use rand::{Rng, SeedableRng};
+
+/// The random number generator of input generators. The same seed always
+/// generates the same input.
+pub fn rng(seed: u64) -> impl rand::Rng {
+    rand_chacha::ChaCha8Rng::seed_from_u64(seed)
+}
+
+// Characters that are meaningful to at least one day's parser.
+const MUTATIONS: &[char] = &[
+    '0', '1', '9', '-', ',', ' ', '\n', '\r', 'a', 'Z', '$', '[', ']', '/', 'é',
+];
+
+/// Replaces, inserts or removes a few characters of `input`, for inputs that
+/// are almost, but not quite, valid.
+pub fn mutate(seed: u64, input: &str) -> String {
+    let mut rng = rng(seed);
+    let mut chars = input.chars().collect::<Vec<_>>();
+    for _ in 0..rng.gen_range(1..=4) {
+        let i = rng.gen_range(0..=chars.len());
+        let c = MUTATIONS[rng.gen_range(0..MUTATIONS.len())];
+        match rng.gen_range(0..3) {
+            0 if i < chars.len() => chars[i] = c,
+            1 if i < chars.len() => {
+                chars.remove(i);
+            }
+            _ => chars.insert(i, c),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutates_deterministically() {
+        let input = "1000\n2000\n\n3000\n";
+        for seed in 0..20 {
+            assert_eq!(mutate(seed, input), mutate(seed, input));
+            assert_ne!(mutate(seed, input), input, "seed {seed}");
+        }
+    }
+}
@@ -1,3 +1,9 @@
+mod parse;
+mod sparse;
+
+pub use parse::ParseError;
+pub use sparse::SparseGrid;
+
 use std::ops::{Index, IndexMut};
 
 #[derive(Clone, Copy, Debug, Default, derive_more::Display, PartialEq, Eq, Hash, derive_more::From, derive_more::Into)]
@@ -7,6 +13,18 @@ pub struct GridPos {
     pub col: usize,
 }
 
+impl GridPos {
+    /// The position moved by `delta`, or `None` if it moves above the first
+    /// row or left of the first column.
+    pub fn checked_add(self, delta: GridPosDelta) -> Option<GridPos> {
+        let row = self.row.checked_add_signed(delta.row_delta)?;
+        let col = self.col.checked_add_signed(delta.col_delta)?;
+        Some((row, col).into())
+    }
+}
+
+// Wraps around when moving above the first row or left of the first column,
+// use checked_add or Grid::offset to walk off the edges.
 impl std::ops::Add<GridPosDelta> for GridPos {
     type Output = GridPos;
 
@@ -50,6 +68,16 @@ impl std::ops::Add<GridPosDelta> for GridPosISize {
     }
 }
 
+impl std::ops::Sub<GridPosISize> for GridPosISize {
+    type Output = GridPosDelta;
+
+    fn sub(self, rhs: GridPosISize) -> Self::Output {
+        let row = self.row - rhs.row;
+        let col = self.col - rhs.col;
+        (row, col).into()
+    }
+}
+
 impl std::ops::AddAssign<GridPosDelta> for GridPosISize {
     fn add_assign(&mut self, rhs: GridPosDelta) {
         *self = *self + rhs;
@@ -123,6 +151,10 @@ pub enum GridIterDirection {
 }
 
 impl GridIterDirection {
+    pub fn iter() -> enum_iterator::All<GridIterDirection> {
+        enum_iterator::all::<GridIterDirection>()
+    }
+
     pub fn delta(&self) -> GridPosDelta {
         match self {
             GridIterDirection::Right => (0, 1),
@@ -156,6 +188,11 @@ impl Direction9 {
         enum_iterator::next(self)
     }
 
+    // All directions except the center.
+    pub fn neighbors() -> impl Iterator<Item = Direction9> + Clone {
+        Self::iter().filter(|dir| !matches!(dir, Self::Center))
+    }
+
     pub fn delta(&self) -> GridPosDelta {
         match self {
             Self::UpLeft => (-1, -1),
@@ -172,6 +209,29 @@ impl Direction9 {
     }
 }
 
+/// A step from a position to one of its neighbors.
+pub trait Direction {
+    fn delta(&self) -> GridPosDelta;
+}
+
+impl Direction for GridIterDirection {
+    fn delta(&self) -> GridPosDelta {
+        GridIterDirection::delta(self)
+    }
+}
+
+impl Direction for Direction9 {
+    fn delta(&self) -> GridPosDelta {
+        Direction9::delta(self)
+    }
+}
+
+impl Direction for GridPosDelta {
+    fn delta(&self) -> GridPosDelta {
+        *self
+    }
+}
+
 type BoxedAxisPosIter<'a> = Box<dyn Iterator<Item = GridPos> + 'a>;
 type BoxedGridPosIter<'a> = Box<dyn Iterator<Item = BoxedAxisPosIter<'a>> + 'a>;
 
@@ -199,6 +259,88 @@ impl<V> Grid<V> {
     pub fn get_element_index(&self, pos: GridPos) -> usize {
         pos.row * self.cols + pos.col
     }
+
+    pub fn contains(&self, pos: GridPos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    pub fn get(&self, pos: GridPos) -> Option<&V> {
+        match self.contains(pos) {
+            true => self.g.get(self.get_element_index(pos)),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: GridPos) -> Option<&mut V> {
+        match self.contains(pos) {
+            true => {
+                let index = self.get_element_index(pos);
+                self.g.get_mut(index)
+            }
+            false => None,
+        }
+    }
+
+    // The position moved by delta, or None if it leaves the grid.
+    pub fn offset(&self, pos: GridPos, delta: GridPosDelta) -> Option<GridPos> {
+        pos.checked_add(delta).filter(|pos| self.contains(*pos))
+    }
+
+    // The position moved by delta, coming back on the opposite edge when it
+    // leaves the grid. The grid must not be empty.
+    fn wrapping_offset(&self, pos: GridPos, delta: GridPosDelta) -> GridPos {
+        let wrap = |i: usize, delta: isize, len: usize| {
+            (i as isize + delta).rem_euclid(len as isize) as usize
+        };
+        let row = wrap(pos.row, delta.row_delta, self.rows);
+        let col = wrap(pos.col, delta.col_delta, self.cols);
+        (row, col).into()
+    }
+
+    // The neighbors up, down, left and right of pos, that are in the grid.
+    pub fn neighbors4(&self, pos: GridPos) -> impl Iterator<Item = (GridPos, &V)> {
+        self.neighbors_with(pos, GridIterDirection::iter())
+    }
+
+    // The neighbors of pos including the diagonal ones, that are in the grid.
+    pub fn neighbors8(&self, pos: GridPos) -> impl Iterator<Item = (GridPos, &V)> {
+        self.neighbors_with(pos, Direction9::neighbors())
+    }
+
+    // The neighbors of pos in the given directions, that are in the grid.
+    pub fn neighbors_with<'a, D: Direction>(
+        &'a self,
+        pos: GridPos,
+        dirs: impl IntoIterator<Item = D> + 'a,
+    ) -> impl Iterator<Item = (GridPos, &'a V)> + 'a {
+        self.neighbors_with_outside(pos, dirs).flatten()
+    }
+
+    // The neighbors of pos in the given directions, with None for the ones
+    // outside of the grid, so they stay in the order of the directions.
+    pub fn neighbors_with_outside<'a, D: Direction>(
+        &'a self,
+        pos: GridPos,
+        dirs: impl IntoIterator<Item = D> + 'a,
+    ) -> impl Iterator<Item = Option<(GridPos, &'a V)>> + 'a {
+        dirs.into_iter()
+            .map(move |dir| self.offset(pos, dir.delta()).map(|pos| (pos, &self[pos])))
+    }
+
+    // The neighbors of pos in the given directions, on a grid whose opposite
+    // edges touch, like a torus.
+    pub fn neighbors_with_wrapping<'a, D: Direction>(
+        &'a self,
+        pos: GridPos,
+        dirs: impl IntoIterator<Item = D> + 'a,
+    ) -> impl Iterator<Item = (GridPos, &'a V)> + 'a {
+        dirs.into_iter()
+            .filter(move |_| !self.g.is_empty())
+            .map(move |dir| {
+                let pos = self.wrapping_offset(pos, dir.delta());
+                (pos, &self[pos])
+            })
+    }
     
     pub fn get_pos_from_linear_index(&self, index: usize) -> GridPos {
         GridPos { row: index / self.cols, col: index % self.cols }
@@ -270,10 +412,10 @@ impl<V> Grid<V> {
     }
 
     pub fn col_pos_iter_rev(&self, col: usize) -> impl DoubleEndedIterator<Item = GridPos> {
-        self.col_pos_iter_rev_with_bounds(col, 0..self.cols)
+        self.col_pos_iter_rev_with_bounds(col, 0..self.rows)
     }
 
-    pub fn pos_iter_dynamic_dispatch(&self, dir: &GridIterDirection) -> BoxedGridPosIter {
+    pub fn pos_iter_dynamic_dispatch(&self, dir: &GridIterDirection) -> BoxedGridPosIter<'_> {
         match dir {
             GridIterDirection::Right => Box::new(
                 (0..self.rows).map(|row| Box::new(self.row_pos_iter(row)) as BoxedAxisPosIter),
@@ -412,3 +554,69 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_off_the_edges() {
+        let mut grid = Grid::<u8>::new(2, 3);
+        let origin = GridPos::default();
+        let up = GridIterDirection::Up.delta();
+        let right = GridIterDirection::Right.delta();
+
+        assert_eq!(origin.checked_add(up), None);
+        assert_eq!(grid.offset(origin, up), None);
+        assert_eq!(grid.offset(origin, right * 2), Some((0, 2).into()));
+        assert_eq!(grid.offset(origin, right * 3), None);
+        assert_eq!(grid.offset((1, 2).into(), Direction9::DownRight.delta()), None);
+
+        *grid.get_mut((1, 2).into()).unwrap() = 7;
+        assert_eq!(grid.get((1, 2).into()), Some(&7));
+        assert_eq!(grid.get((2, 0).into()), None);
+        assert_eq!(grid.get((0, 3).into()), None);
+        assert!(grid.get_mut((0, 3).into()).is_none());
+        assert!(grid.contains((1, 0).into()));
+        assert!(!grid.contains((1, 3).into()));
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let mut grid = Grid::<usize>::new(3, 3);
+        (0..9).for_each(|i| grid.g[i] = i);
+        let values = |neighbors: &mut dyn Iterator<Item = (GridPos, &usize)>| {
+            neighbors.map(|(_, v)| *v).collect::<Vec<_>>()
+        };
+
+        assert_eq!(values(&mut grid.neighbors4((1, 1).into())), [5, 3, 7, 1]);
+        assert_eq!(values(&mut grid.neighbors4((0, 0).into())), [1, 3]);
+        assert_eq!(values(&mut grid.neighbors8((0, 2).into())), [5, 4, 1]);
+        assert_eq!(grid.neighbors8((1, 1).into()).count(), 8);
+
+        let diagonal = [Direction9::UpLeft, Direction9::DownRight];
+        assert_eq!(
+            values(&mut grid.neighbors_with((0, 0).into(), diagonal)),
+            [4]
+        );
+        assert_eq!(
+            values(&mut grid.neighbors_with_wrapping((0, 0).into(), diagonal)),
+            [8, 4]
+        );
+        assert_eq!(
+            values(&mut grid.neighbors_with_wrapping((2, 0).into(), GridIterDirection::iter())),
+            [7, 8, 0, 3]
+        );
+        let outside = grid
+            .neighbors_with_outside((0, 0).into(), diagonal)
+            .map(|n| n.map(|(pos, _)| pos))
+            .collect::<Vec<_>>();
+        assert_eq!(outside, [None, Some((1, 1).into())]);
+
+        let empty = Grid::<usize>::new(0, 0);
+        assert_eq!(
+            empty.neighbors_with_wrapping((0, 0).into(), Direction9::iter()).count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use super::Grid;
+use crate::error::{BoxError, Locate, Result};
+
+impl<V> Grid<V> {
+    /// Parses a grid of one character per cell, with as many rows as lines
+    /// and as many columns as characters in the first line. Lines can end
+    /// with CRLF, and trailing empty lines are ignored.
+    ///
+    /// Errors point at the offending part of `s`, be it a character that
+    /// `parse_cell` rejects or a row of a different length than the first one.
+    pub fn parse<F, E>(s: &str, mut parse_cell: F) -> Result<Grid<V>>
+    where
+        F: FnMut(char) -> std::result::Result<V, E>,
+        E: Into<BoxError>,
+    {
+        let lines = s
+            .trim_end_matches(['\n', '\r'])
+            .lines()
+            .collect::<Vec<_>>();
+        let cols = lines.first().map_or(0, |l| l.chars().count());
+        if cols == 0 {
+            return Err(ParseError::Empty).locate(s, s);
+        }
+
+        let mut g = Vec::with_capacity(lines.len() * cols);
+        for (row, l) in lines.iter().enumerate() {
+            let ragged = || ParseError::RaggedRow { row: row + 1, cols };
+            for (col, (i, c)) in l.char_indices().enumerate() {
+                if col >= cols {
+                    return Err(ragged()).locate(s, &l[i..]);
+                }
+                g.push(parse_cell(c).locate(s, &l[i..i + c.len_utf8()])?);
+            }
+            if l.chars().count() < cols {
+                return Err(ragged()).locate(s, l);
+            }
+        }
+        Ok(Grid {
+            g,
+            rows: lines.len(),
+            cols,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("Empty grid")]
+    Empty,
+    #[error("Row {row} doesn't have {cols} columns like the first row")]
+    RaggedRow { row: usize, cols: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit(c: char) -> std::result::Result<u32, String> {
+        c.to_digit(10).ok_or_else(|| format!("Invalid digit {}", c))
+    }
+
+    #[test]
+    fn parses_rectangular_grids() {
+        for s in ["123\n456", "123\n456\n", "123\r\n456\r\n", "123\n456\n\n\n"] {
+            let grid = Grid::parse(s, digit).unwrap();
+            assert_eq!((grid.rows, grid.cols), (2, 3), "{s:?}");
+            assert_eq!(grid[(1, 0).into()], 4);
+            assert_eq!(grid.to_string(), "123\n456\n");
+        }
+    }
+
+    #[test]
+    fn locates_malformed_grids() {
+        let cases = [
+            ("12\n3x", (2, 2), "Invalid digit x"),
+            (
+                "12\n345",
+                (2, 3),
+                "Row 2 doesn't have 2 columns like the first row",
+            ),
+            (
+                "123\r\n45\r\n678",
+                (2, 1),
+                "Row 2 doesn't have 3 columns like the first row",
+            ),
+            (
+                "12\n\n34",
+                (2, 1),
+                "Row 2 doesn't have 2 columns like the first row",
+            ),
+            ("", (1, 1), "Empty grid"),
+            ("\n12", (1, 1), "Empty grid"),
+        ];
+        for (s, expected, message) in cases {
+            let err = Grid::parse(s, digit).unwrap_err();
+            let location = err.location().unwrap();
+            assert_eq!((location.line, location.column), expected, "{s:?}");
+            assert_eq!(err.diagnostic("grid.txt").unwrap().message, message);
+        }
+    }
+}
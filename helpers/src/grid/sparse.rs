@@ -0,0 +1,253 @@
+use super::{Direction, Direction9, EnumIterator, GridIterDirection, GridPosISize};
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut, RangeInclusive};
+
+/// A grid without bounds, for simulations on an infinite plane. Only the
+/// written cells are stored, every other cell holds the default value.
+///
+/// The iteration API mirrors the one of [`Grid`](super::Grid), over the
+/// bounding box of the written cells.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<V> {
+    cells: HashMap<GridPosISize, V>,
+    default: V,
+    // Top left and bottom right corners of the written cells.
+    bounds: Option<(GridPosISize, GridPosISize)>,
+}
+
+impl<V> Default for SparseGrid<V>
+where
+    V: Default,
+{
+    fn default() -> Self {
+        Self::with_default(V::default())
+    }
+}
+
+impl<V> SparseGrid<V>
+where
+    V: Default,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<V> SparseGrid<V> {
+    /// A grid where every cell that isn't written holds `default`.
+    pub fn with_default(default: V) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// The number of written cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The value of a written cell.
+    pub fn get(&self, pos: GridPosISize) -> Option<&V> {
+        self.cells.get(&pos)
+    }
+
+    /// Writes a cell, growing the bounding box to contain it. Returns the
+    /// previous value if the cell was already written.
+    pub fn insert(&mut self, pos: GridPosISize, value: V) -> Option<V> {
+        self.grow(pos);
+        self.cells.insert(pos, value)
+    }
+
+    /// Removes every written cell.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    /// The top left and bottom right corners of the written cells, both
+    /// included.
+    pub fn bounds(&self) -> Option<(GridPosISize, GridPosISize)> {
+        self.bounds
+    }
+
+    fn grow(&mut self, pos: GridPosISize) {
+        let (min, max) = self.bounds.get_or_insert((pos, pos));
+        min.row = min.row.min(pos.row);
+        min.col = min.col.min(pos.col);
+        max.row = max.row.max(pos.row);
+        max.col = max.col.max(pos.col);
+    }
+
+    /// The rows of the bounding box, empty when nothing was written.
+    pub fn row_range(&self) -> RangeInclusive<isize> {
+        match self.bounds {
+            Some((min, max)) => min.row..=max.row,
+            None => Self::empty_range(),
+        }
+    }
+
+    /// The columns of the bounding box, empty when nothing was written.
+    pub fn col_range(&self) -> RangeInclusive<isize> {
+        match self.bounds {
+            Some((min, max)) => min.col..=max.col,
+            None => Self::empty_range(),
+        }
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    fn empty_range() -> RangeInclusive<isize> {
+        0..=-1
+    }
+
+    /// The neighbors up, down, left and right of `pos`.
+    pub fn neighbors4(&self, pos: GridPosISize) -> impl Iterator<Item = (GridPosISize, &V)> {
+        self.neighbors_with(pos, GridIterDirection::iter())
+    }
+
+    /// The neighbors of `pos` including the diagonal ones.
+    pub fn neighbors8(&self, pos: GridPosISize) -> impl Iterator<Item = (GridPosISize, &V)> {
+        self.neighbors_with(pos, Direction9::neighbors())
+    }
+
+    /// The neighbors of `pos` in the given directions. The plane has no edges,
+    /// so there is one for each direction.
+    pub fn neighbors_with<'a, D: Direction>(
+        &'a self,
+        pos: GridPosISize,
+        dirs: impl IntoIterator<Item = D> + 'a,
+    ) -> impl Iterator<Item = (GridPosISize, &'a V)> + 'a {
+        dirs.into_iter().map(move |dir| {
+            let pos = pos + dir.delta();
+            (pos, &self[pos])
+        })
+    }
+
+    /// The written cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (GridPosISize, &V)> {
+        self.cells.iter().map(|(pos, value)| (*pos, value))
+    }
+
+    // Row major
+    pub fn row_pos_iter(&self, row: isize) -> impl DoubleEndedIterator<Item = GridPosISize> {
+        self.col_range().map(move |col| (row, col).into())
+    }
+
+    pub fn row_pos_iter_rev(&self, row: isize) -> impl DoubleEndedIterator<Item = GridPosISize> {
+        self.row_pos_iter(row).rev()
+    }
+
+    // Column major
+    pub fn col_pos_iter(&self, col: isize) -> impl DoubleEndedIterator<Item = GridPosISize> {
+        self.row_range().map(move |row| (row, col).into())
+    }
+
+    pub fn col_pos_iter_rev(&self, col: isize) -> impl DoubleEndedIterator<Item = GridPosISize> {
+        self.col_pos_iter(col).rev()
+    }
+
+    pub fn pos_iter_along_axis(
+        &self,
+        axis_index: isize,
+        dir: &GridIterDirection,
+    ) -> EnumIterator<
+        impl Iterator<Item = GridPosISize>,
+        impl Iterator<Item = GridPosISize>,
+        impl Iterator<Item = GridPosISize>,
+        impl Iterator<Item = GridPosISize>,
+    > {
+        match dir {
+            GridIterDirection::Right => EnumIterator::V1(self.row_pos_iter(axis_index)),
+            GridIterDirection::Left => EnumIterator::V2(self.row_pos_iter_rev(axis_index)),
+            GridIterDirection::Down => EnumIterator::V3(self.col_pos_iter(axis_index)),
+            GridIterDirection::Up => EnumIterator::V4(self.col_pos_iter_rev(axis_index)),
+        }
+    }
+
+    pub fn grid_pos_iter(
+        &self,
+        dir: &GridIterDirection,
+    ) -> impl Iterator<Item = impl Iterator<Item = GridPosISize>> + '_ {
+        let dir = *dir;
+        let axes = match dir {
+            GridIterDirection::Right | GridIterDirection::Left => self.row_range(),
+            GridIterDirection::Down | GridIterDirection::Up => self.col_range(),
+        };
+        axes.map(move |axis_index| self.pos_iter_along_axis(axis_index, &dir))
+    }
+}
+
+impl<V> std::fmt::Display for SparseGrid<V>
+where
+    V: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid_pos_iter(&GridIterDirection::Right)
+            .try_for_each(|mut row| {
+                row.try_for_each(|pos| write!(f, "{}", self[pos]))?;
+                writeln!(f)
+            })
+    }
+}
+
+impl<V> Index<GridPosISize> for SparseGrid<V> {
+    type Output = V;
+
+    fn index(&self, pos: GridPosISize) -> &Self::Output {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+}
+
+impl<V> IndexMut<GridPosISize> for SparseGrid<V>
+where
+    V: Clone,
+{
+    fn index_mut(&mut self, pos: GridPosISize) -> &mut Self::Output {
+        self.grow(pos);
+        self.cells
+            .entry(pos)
+            .or_insert_with(|| self.default.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_on_write() {
+        let mut grid = SparseGrid::with_default('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+        assert_eq!(grid[(-100, 100).into()], '.');
+
+        grid[(-1, -2).into()] = '#';
+        grid.insert((1, 0).into(), 'H');
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some(((-1, -2).into(), (1, 0).into())));
+        assert_eq!(grid.get((0, 0).into()), None);
+        assert_eq!(grid.to_string(), "#..\n...\n..H\n");
+
+        let up = grid
+            .grid_pos_iter(&GridIterDirection::Up)
+            .map(|col| col.map(|pos| grid[pos]).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(up, ["..#", "...", "H.."]);
+
+        let neighbors = grid
+            .neighbors8((0, -1).into())
+            .filter(|(_, tile)| **tile != '.')
+            .collect::<Vec<_>>();
+        assert_eq!(neighbors, [((-1, -2).into(), &'#'), ((1, 0).into(), &'H')]);
+        assert_eq!(grid.neighbors4((5, 5).into()).count(), 4);
+
+        grid.clear();
+        assert!(grid.is_empty());
+        assert_eq!(grid.row_range().count(), 0);
+    }
+}
@@ -1 +1,7 @@
+pub mod error;
+pub mod fixture;
+pub mod generate;
 pub mod grid;
+pub mod params;
+pub mod search;
+pub mod solution;
@@ -0,0 +1,195 @@
+//! Tunable constants of puzzles, like the length of the rope of day 9, so
+//! that variants of a puzzle can be explored without recompiling.
+
+use crate::{error::BoxError, solution::Part};
+use std::str::FromStr;
+
+/// Typed parameters of a puzzle, declared with [`params!`](crate::params!).
+/// Their defaults are the values of the puzzle.
+pub trait Params: Default {
+    /// Names and values of the parameters.
+    fn values(&self) -> Vec<(&'static str, String)>;
+
+    /// Sets the parameter `name` from its textual `value`.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error>;
+}
+
+impl Params for () {
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), Error> {
+        Err(Error::unknown(name, &[]))
+    }
+}
+
+pub fn parse_value<T>(name: &str, value: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    value.trim().parse().map_err(|e: T::Err| Error::InvalidValue {
+        name: name.to_owned(),
+        value: value.to_owned(),
+        source: e.into(),
+    })
+}
+
+/// Declares a struct of puzzle parameters along with their defaults, and
+/// implements [`Params`] for it.
+///
+/// ```
+/// helpers::params! {
+///     pub struct Params {
+///         window: usize = 4,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: $ty),*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $($field: $default),* }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            fn values(&self) -> Vec<(&'static str, String)> {
+                vec![$((stringify!($field), self.$field.to_string())),*]
+            }
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), $crate::params::Error> {
+                match name {
+                    $(stringify!($field) => self.$field = $crate::params::parse_value(name, value)?,)*
+                    _ => return Err($crate::params::Error::unknown(name, &[$(stringify!($field)),*])),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// A parameter with a value for each part. Parses from a single value used by
+/// both parts, like `25`, or from one value per part, like `2,10`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerPart<T> {
+    pub part1: T,
+    pub part2: T,
+}
+
+impl<T> PerPart<T> {
+    pub const fn new(part1: T, part2: T) -> Self {
+        Self { part1, part2 }
+    }
+
+    pub fn get(&self, part: Part) -> &T {
+        match part {
+            Part::One => &self.part1,
+            Part::Two => &self.part2,
+        }
+    }
+}
+
+impl<T> FromStr for PerPart<T>
+where
+    T: FromStr + Clone,
+    T::Err: Into<BoxError>,
+{
+    type Err = BoxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |v: &str| v.trim().parse::<T>().map_err(Into::into);
+        match s.split_once(',') {
+            Some((part1, part2)) => Ok(Self::new(parse(part1)?, parse(part2)?)),
+            None => {
+                let value = parse(s)?;
+                Ok(Self::new(value.clone(), value))
+            }
+        }
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for PerPart<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.part1, self.part2)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("unknown parameter '{name}', {}", expected_names(.expected))]
+    Unknown { name: String, expected: Vec<String> },
+    #[error("invalid value '{value}' of parameter '{name}'")]
+    InvalidValue {
+        name: String,
+        value: String,
+        #[source]
+        source: BoxError,
+    },
+}
+
+impl Error {
+    pub fn unknown(name: &str, expected: &[&str]) -> Self {
+        Error::Unknown {
+            name: name.to_owned(),
+            expected: expected.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+}
+
+fn expected_names(expected: &[String]) -> String {
+    match expected.is_empty() {
+        true => "the puzzle has no parameters".to_owned(),
+        false => format!("expected one of: {}", expected.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params! {
+        struct Test {
+            window: usize = 4,
+            knots: PerPart<usize> = PerPart::new(2, 10),
+        }
+    }
+
+    #[test]
+    fn sets_typed_values() {
+        let mut params = Test::default();
+        assert_eq!(
+            params.values(),
+            [("window", "4".to_owned()), ("knots", "2,10".to_owned())]
+        );
+
+        params.set("window", "14").unwrap();
+        params.set("knots", "25").unwrap();
+        assert_eq!(params.window, 14);
+        assert_eq!(params.knots, PerPart::new(25, 25));
+        params.set("knots", "3, 7").unwrap();
+        assert_eq!(*params.knots.get(Part::Two), 7);
+
+        let err = params.set("window", "-1").unwrap_err();
+        assert_eq!(err.to_string(), "invalid value '-1' of parameter 'window'");
+        let err = params.set("size", "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown parameter 'size', expected one of: window, knots"
+        );
+        assert!(().set("size", "1").is_err());
+    }
+}
@@ -0,0 +1,353 @@
+//! Shortest paths over a [`Grid`], moving to the neighbors of a cell in the
+//! given directions, like `GridIterDirection::iter()` for up, down, left and
+//! right moves or `Direction9::neighbors()` to also move diagonally.
+//!
+//! Every search starts from any number of cells, and the ones outside of the
+//! grid are ignored.
+
+use crate::grid::{Direction, Grid, GridPos};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// A shortest path, with the cells it goes through from its start to its
+/// goal, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub distance: u32,
+    pub positions: Vec<GridPos>,
+}
+
+// The distance of each reached cell, and the cell it was reached from.
+struct Explored {
+    distances: Grid<Option<u32>>,
+    previous: Grid<Option<GridPos>>,
+}
+
+impl Explored {
+    fn new<V>(grid: &Grid<V>) -> Self {
+        Explored {
+            distances: Grid::new(grid.rows, grid.cols),
+            previous: Grid::new(grid.rows, grid.cols),
+        }
+    }
+
+    fn path_to(&self, goal: GridPos) -> Option<Path> {
+        let distance = (*self.distances.get(goal)?)?;
+        let mut positions = vec![goal];
+        while let Some(previous) = self.previous[positions[positions.len() - 1]] {
+            positions.push(previous);
+        }
+        positions.reverse();
+        Some(Path {
+            distance,
+            positions,
+        })
+    }
+}
+
+/// The manhattan distance between two cells, an A* heuristic for up, down,
+/// left and right moves costing at least 1.
+pub fn manhattan_distance(a: GridPos, b: GridPos) -> u32 {
+    (a.row.abs_diff(b.row) + a.col.abs_diff(b.col)) as u32
+}
+
+/// The chebyshev distance between two cells, an A* heuristic when moving
+/// diagonally too, with moves costing at least 1.
+pub fn chebyshev_distance(a: GridPos, b: GridPos) -> u32 {
+    a.row.abs_diff(b.row).max(a.col.abs_diff(b.col)) as u32
+}
+
+fn explore_bfs<V, D, M, F>(
+    grid: &Grid<V>,
+    starts: impl IntoIterator<Item = GridPos>,
+    goal: Option<GridPos>,
+    moves: M,
+    mut can_move: F,
+) -> Explored
+where
+    D: Direction,
+    M: IntoIterator<Item = D> + Clone,
+    F: FnMut(&V, &V) -> bool,
+{
+    let mut explored = Explored::new(grid);
+    let mut queue = VecDeque::new();
+    for start in starts.into_iter().filter(|start| grid.contains(*start)) {
+        explored.distances[start] = Some(0);
+        queue.push_back(start);
+    }
+    while let Some(pos) = queue.pop_front() {
+        if Some(pos) == goal {
+            break;
+        }
+        let distance = explored.distances[pos].unwrap_or_default();
+        for (next, next_value) in grid.neighbors_with(pos, moves.clone()) {
+            if explored.distances[next].is_none() && can_move(&grid[pos], next_value) {
+                explored.distances[next] = Some(distance + 1);
+                explored.previous[next] = Some(pos);
+                queue.push_back(next);
+            }
+        }
+    }
+    explored
+}
+
+// Dijkstra, or A* given a heuristic that never overestimates the distance
+// left to the goal.
+fn explore_best_first<V, D, M, F, H>(
+    grid: &Grid<V>,
+    starts: impl IntoIterator<Item = GridPos>,
+    goal: Option<GridPos>,
+    moves: M,
+    mut cost: F,
+    mut heuristic: H,
+) -> Explored
+where
+    D: Direction,
+    M: IntoIterator<Item = D> + Clone,
+    F: FnMut(&V, &V) -> Option<u32>,
+    H: FnMut(GridPos) -> u32,
+{
+    let mut explored = Explored::new(grid);
+    // Positions are queued by their linear index, as they aren't ordered.
+    let mut queue = BinaryHeap::new();
+    for start in starts.into_iter().filter(|start| grid.contains(*start)) {
+        explored.distances[start] = Some(0);
+        queue.push(Reverse((
+            heuristic(start),
+            0,
+            grid.get_element_index(start),
+        )));
+    }
+    while let Some(Reverse((_, distance, index))) = queue.pop() {
+        let pos = grid.get_pos_from_linear_index(index);
+        // A shorter path to it was found after it was queued.
+        if explored.distances[pos].is_some_and(|d| d < distance) {
+            continue;
+        }
+        if Some(pos) == goal {
+            break;
+        }
+        for (next, next_value) in grid.neighbors_with(pos, moves.clone()) {
+            let Some(next_distance) =
+                cost(&grid[pos], next_value).and_then(|step| distance.checked_add(step))
+            else {
+                continue;
+            };
+            if explored.distances[next].is_none_or(|d| next_distance < d) {
+                explored.distances[next] = Some(next_distance);
+                explored.previous[next] = Some(pos);
+                let priority = next_distance.saturating_add(heuristic(next));
+                queue.push(Reverse((
+                    priority,
+                    next_distance,
+                    grid.get_element_index(next),
+                )));
+            }
+        }
+    }
+    explored
+}
+
+/// The shortest path from the closest of `starts` to `goal`, where every move
+/// costs 1. `can_move` tells whether one can move from a cell to a neighbor,
+/// given their values.
+pub fn bfs<V, D, M, F>(
+    grid: &Grid<V>,
+    starts: impl IntoIterator<Item = GridPos>,
+    goal: GridPos,
+    moves: M,
+    can_move: F,
+) -> Option<Path>
+where
+    D: Direction,
+    M: IntoIterator<Item = D> + Clone,
+    F: FnMut(&V, &V) -> bool,
+{
+    explore_bfs(grid, starts, Some(goal), moves, can_move).path_to(goal)
+}
+
+/// The distance of every cell from the closest of `starts`, where every move
+/// costs 1, or `None` for the cells that can't be reached.
+pub fn bfs_distances<V, D, M, F>(
+    grid: &Grid<V>,
+    starts: impl IntoIterator<Item = GridPos>,
+    moves: M,
+    can_move: F,
+) -> Grid<Option<u32>>
+where
+    D: Direction,
+    M: IntoIterator<Item = D> + Clone,
+    F: FnMut(&V, &V) -> bool,
+{
+    explore_bfs(grid, starts, None, moves, can_move).distances
+}
+
+/// The cheapest path from the closest of `starts` to `goal`. `cost` gives the
+/// cost of moving from a cell to a neighbor given their values, or `None`
+/// when one can't move there.
+pub fn dijkstra<V, D, M, F>(
+    grid: &Grid<V>,
+    starts: impl IntoIterator<Item = GridPos>,
+    goal: GridPos,
+    moves: M,
+    cost: F,
+) -> Option<Path>
+where
+    D: Direction,
+    M: IntoIterator<Item = D> + Clone,
+    F: FnMut(&V, &V) -> Option<u32>,
+{
+    explore_best_first(grid, starts, Some(goal), moves, cost, |_| 0).path_to(goal)
+}
+
+/// The cost of the cheapest path to every cell from the closest of `starts`,
+/// or `None` for the cells that can't be reached.
+pub fn dijkstra_distances<V, D, M, F>(
+    grid: &Grid<V>,
+    starts: impl IntoIterator<Item = GridPos>,
+    moves: M,
+    cost: F,
+) -> Grid<Option<u32>>
+where
+    D: Direction,
+    M: IntoIterator<Item = D> + Clone,
+    F: FnMut(&V, &V) -> Option<u32>,
+{
+    explore_best_first(grid, starts, None, moves, cost, |_| 0).distances
+}
+
+/// Like [`dijkstra`], exploring first the cells that `heuristic` estimates
+/// closer to `goal`. The path is the cheapest one as long as the heuristic
+/// never overestimates the cost left, like [`manhattan_distance`] does for
+/// up, down, left and right moves costing at least 1.
+pub fn astar<V, D, M, F, H>(
+    grid: &Grid<V>,
+    starts: impl IntoIterator<Item = GridPos>,
+    goal: GridPos,
+    moves: M,
+    cost: F,
+    heuristic: H,
+) -> Option<Path>
+where
+    D: Direction,
+    M: IntoIterator<Item = D> + Clone,
+    F: FnMut(&V, &V) -> Option<u32>,
+    H: FnMut(GridPos) -> u32,
+{
+    explore_best_first(grid, starts, Some(goal), moves, cost, heuristic).path_to(goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Direction9, GridIterDirection};
+
+    fn grid(s: &str) -> Grid<char> {
+        Grid::parse(s, Ok::<_, std::convert::Infallible>).unwrap()
+    }
+
+    fn open(_: &char, to: &char) -> bool {
+        *to != '#'
+    }
+
+    // Entering a digit costs its value, walls can't be entered.
+    fn digit_cost(_: &char, to: &char) -> Option<u32> {
+        to.to_digit(10)
+    }
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.";
+
+    #[test]
+    fn bfs_finds_shortest_paths() {
+        let maze = grid(MAZE);
+        let (start, goal) = ((0, 0).into(), (0, 6).into());
+        let path = bfs(&maze, [start], goal, GridIterDirection::iter(), open).unwrap();
+        assert_eq!(path.distance, 12);
+        assert_eq!(path.positions.len(), 13);
+        assert_eq!(path.positions[0], start);
+        assert_eq!(path.positions[12], goal);
+        assert!(path
+            .positions
+            .windows(2)
+            .all(|w| manhattan_distance(w[0], w[1]) == 1 && maze[w[1]] != '#'));
+
+        let path = bfs(&maze, [start], goal, Direction9::neighbors(), open).unwrap();
+        assert_eq!(path.distance, 7);
+
+        // The closest start wins.
+        let starts = [start, (3, 6).into()];
+        let path = bfs(&maze, starts, goal, GridIterDirection::iter(), open).unwrap();
+        assert_eq!((path.distance, path.positions[0]), (3, (3, 6).into()));
+
+        let walled = grid(".#.");
+        let moves = GridIterDirection::iter;
+        assert_eq!(
+            bfs(&walled, [(0, 0).into()], (0, 2).into(), moves(), open),
+            None
+        );
+        assert_eq!(
+            bfs(&walled, [(5, 5).into()], (0, 0).into(), moves(), open),
+            None
+        );
+    }
+
+    #[test]
+    fn bfs_maps_all_distances() {
+        let maze = grid(".#.\n...");
+        let distances = bfs_distances(&maze, [(0, 0).into()], GridIterDirection::iter(), open);
+        let rows = (0..maze.rows)
+            .map(|row| {
+                maze.row_pos_iter(row)
+                    .map(|pos| distances[pos].map_or('-', |d| char::from_digit(d, 10).unwrap()))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows, ["0-4", "123"]);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_find_cheapest_paths() {
+        let costs = grid("1163751\n1381373\n2136511\n3694931\n7463417");
+        let (start, goal) = ((0, 0).into(), (4, 6).into());
+        let moves = GridIterDirection::iter;
+
+        let path = dijkstra(&costs, [start], goal, moves(), digit_cost).unwrap();
+        assert_eq!(path.distance, 28);
+        let entered = path.positions[1..]
+            .iter()
+            .map(|pos| costs[*pos].to_digit(10).unwrap());
+        assert_eq!(entered.sum::<u32>(), path.distance);
+
+        let heuristic = |pos| manhattan_distance(pos, goal);
+        let astar_path = astar(&costs, [start], goal, moves(), digit_cost, heuristic).unwrap();
+        assert_eq!(astar_path.distance, 28);
+
+        let distances = dijkstra_distances(&costs, [start], moves(), digit_cost);
+        assert_eq!(distances[goal], Some(28));
+        assert_eq!(distances[start], Some(0));
+        assert_eq!(distances[(0, 1).into()], Some(1));
+
+        let walled = grid("1#1");
+        assert_eq!(
+            dijkstra(&walled, [start], (0, 2).into(), moves(), digit_cost),
+            None
+        );
+        let chebyshev = |pos| chebyshev_distance(pos, (0, 2).into());
+        let moves = Direction9::neighbors();
+        assert_eq!(
+            astar(
+                &walled,
+                [start],
+                (0, 2).into(),
+                moves,
+                digit_cost,
+                chebyshev
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use crate::params::Params;
+use color_eyre::eyre::{eyre, Result};
+use std::time::{Duration, Instant};
+
+/// The answer of a puzzle part.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum Answer {
+    #[display(fmt = "{}", _0)]
+    Number(i128),
+    #[display(fmt = "{}", _0)]
+    Text(String),
+    // Answers that are read off a rendering, like a CRT screen.
+    #[display(fmt = "{}", _0)]
+    Multiline(String),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Multiline(_))
+    }
+}
+
+// Every integer up to 64 bits fits in an i128, so answers never wrap.
+macro_rules! impl_answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(i128::try_from(n).expect("integer answers fit in an i128"))
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum Part {
+    #[display(fmt = "1")]
+    One,
+    #[display(fmt = "2")]
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl std::str::FromStr for Part {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(eyre!("invalid part '{}', expected 1 or 2", s)),
+        }
+    }
+}
+
+/// A puzzle day, split into parsing the input once and solving both parts
+/// from the parsed input.
+pub trait Puzzle {
+    const YEAR: u32;
+    const DAY: u8;
+
+    type Input;
+    /// Tunable constants of the puzzle, `()` when it has none.
+    type Params: Params;
+
+    fn parse(&self, s: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input, params: &Self::Params) -> Result<Answer>;
+    fn part2(&self, input: &Self::Input, params: &Self::Params) -> Result<Answer>;
+
+    fn solve_part(&self, input: &Self::Input, params: &Self::Params, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => self.part1(input, params),
+            Part::Two => self.part2(input, params),
+        }
+    }
+
+    /// A random valid input of roughly `size` lines or items, for days that
+    /// have an input generator.
+    fn generate(&self, _seed: u64, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug)]
+pub struct PartOutcome {
+    pub part: Part,
+    pub answer: Result<Answer>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartOutcome>,
+}
+
+// Wall times of repeated runs of the parse step and of each part.
+#[derive(Debug, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub parts: Vec<(Part, Vec<Duration>)>,
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+/// Type erased view of a `Puzzle`, which is what the `aoc` runner registers.
+pub trait Solution {
+    fn year(&self) -> u32;
+    fn day(&self) -> u8;
+    /// Names and default values of the puzzle parameters.
+    fn params(&self) -> Vec<(&'static str, String)>;
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Outcome> {
+        self.solve_with(input, parts, &[])
+    }
+    /// Solves with the given `(name, value)` parameters instead of the
+    /// defaults.
+    fn solve_with(
+        &self,
+        input: &str,
+        parts: &[Part],
+        params: &[(String, String)],
+    ) -> Result<Outcome>;
+    /// Times `iterations` runs of the parse step and of each part, with the
+    /// given `(name, value)` parameters instead of the defaults.
+    fn sample(
+        &self,
+        input: &str,
+        parts: &[Part],
+        params: &[(String, String)],
+        iterations: usize,
+    ) -> Result<Samples>;
+    fn generate(&self, seed: u64, size: usize) -> Option<String>;
+}
+
+/// The text of an input read as bytes, the way the `aoc` runner loads inputs
+/// and the fuzz targets get theirs.
+pub fn input_from_utf8(bytes: &[u8]) -> std::result::Result<&str, std::str::Utf8Error> {
+    std::str::from_utf8(bytes)
+}
+
+fn puzzle_params<P: Puzzle>(params: &[(String, String)]) -> Result<P::Params> {
+    let mut puzzle_params = P::Params::default();
+    for (name, value) in params {
+        puzzle_params.set(name, value)?;
+    }
+    Ok(puzzle_params)
+}
+
+impl<P: Puzzle> Solution for P {
+    fn year(&self) -> u32 {
+        P::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        P::DAY
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        P::Params::default().values()
+    }
+
+    fn solve_with(
+        &self,
+        input: &str,
+        parts: &[Part],
+        params: &[(String, String)],
+    ) -> Result<Outcome> {
+        let puzzle_params = puzzle_params::<P>(params)?;
+        let (input, parse_elapsed) = time(|| {
+            let _span = tracing::info_span!("parse", bytes = input.len()).entered();
+            self.parse(input)
+        });
+        let input = input?;
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let (answer, elapsed) = time(|| {
+                    let _span = tracing::info_span!("part", part = %part).entered();
+                    self.solve_part(&input, &puzzle_params, part)
+                });
+                PartOutcome {
+                    part,
+                    answer,
+                    elapsed,
+                }
+            })
+            .collect();
+        Ok(Outcome {
+            parse_elapsed,
+            parts,
+        })
+    }
+
+    fn sample(
+        &self,
+        s: &str,
+        parts: &[Part],
+        params: &[(String, String)],
+        iterations: usize,
+    ) -> Result<Samples> {
+        let params = puzzle_params::<P>(params)?;
+        let mut samples = Samples::default();
+        let mut input = self.parse(s)?;
+        for _ in 0..iterations {
+            let (parsed, elapsed) = time(|| self.parse(s));
+            input = parsed?;
+            samples.parse.push(elapsed);
+        }
+
+        for &part in parts {
+            let elapsed = (0..iterations)
+                .map(|_| {
+                    let (answer, elapsed) = time(|| self.solve_part(&input, &params, part));
+                    answer.map(|_| elapsed)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            samples.parts.push((part, elapsed));
+        }
+        Ok(samples)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Puzzle::generate(self, seed, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_numbers_without_wrapping() {
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from(i64::MIN).to_string(), "-9223372036854775808");
+        assert_eq!(Answer::from(42u8), Answer::Number(42));
+    }
+}
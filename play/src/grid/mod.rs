@@ -63,7 +63,7 @@ impl Grid {
     }
 
     #[allow(unused)]
-    fn index_iter_dynamic_dispatch(&self, dir: &GridIterDirection) -> BoxedGridIndexIter {
+    fn index_iter_dynamic_dispatch(&self, dir: &GridIterDirection) -> BoxedGridIndexIter<'_> {
         match dir {
             GridIterDirection::RowsLeftToRight => Box::new(
                 (0..self.rows).map(|row| Box::new(self.row_index_iter(row)) as BoxedAxisIndexIter),